@@ -0,0 +1,71 @@
+use runeterra_database::db::{Db, DbError, LoadOptions, Locale, SchemaMode};
+use std::env;
+use std::process;
+
+const USAGE: &str = "\
+Usage: runeterra <command>
+
+Commands:
+    check [--locale <locale>] [--strict] [<dir>]
+        Validates the embedded database, or the Data Dragon bundle unpacked in <dir>.";
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let code = match args.split_first() {
+        Some((command, args)) if command == "check" => check(args),
+        _ => usage(),
+    };
+    process::exit(code);
+}
+
+fn usage() -> i32 {
+    eprintln!("{}", USAGE);
+    2
+}
+
+fn check(args: &[String]) -> i32 {
+    let mut options = LoadOptions::default();
+    let mut dir = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--locale" => match args.next().map(|locale| locale.parse::<Locale>()) {
+                Some(Ok(locale)) => options.locale = locale,
+                Some(Err(err)) => {
+                    eprintln!("{}", err);
+                    return 2;
+                }
+                None => return usage(),
+            },
+            "--strict" => options.schema = SchemaMode::Strict,
+            _ if arg.starts_with('-') || dir.is_some() => return usage(),
+            _ => dir = Some(arg),
+        }
+    }
+
+    let db = match dir {
+        Some(dir) => Db::from_dir_with_options(dir, options),
+        None => Db::load_with_options(options),
+    };
+    let db = match db {
+        Ok(db) => db,
+        Err(DbError::Schema(report)) => {
+            eprint!("{}", report);
+            return 1;
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            return 2;
+        }
+    };
+
+    let report = runeterra_core::check(&db);
+    if report.is_ok() {
+        println!("{} cards, no problems found", db.collection.0.len());
+        0
+    } else {
+        print!("{}", report);
+        eprintln!("{} problems found", report.0.len());
+        1
+    }
+}
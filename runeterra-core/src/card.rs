@@ -1,7 +1,6 @@
-use crate::DB;
-
 use strum;
 
+use once_cell::sync::Lazy;
 use runeterra_database::db;
 use std::collections::HashMap;
 use std::convert::TryFrom;
@@ -12,6 +11,8 @@ use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum Error {
+    #[error("Invalid card code {code:?}")]
+    InvalidCardCode { code: String },
     #[error("Invalid card code set")]
     InvalidCardCodeSet {
         #[from]
@@ -47,10 +48,21 @@ pub struct Card<'a> {
     pub collectible: bool,
 }
 
-impl<'a> TryFrom<&'a db::Card> for Card<'a> {
-    type Error = crate::card::Error;
+impl<'a> Card<'a> {
+    /// Converts a database card, resolving its keywords in `globals`.
+    ///
+    /// Text fields are borrowed as-is, so they are in the locale `db_card` and `globals` were
+    /// loaded with.
+    pub fn try_from_db(
+        db_card: &'a db::Card,
+        globals: &'a db::Globals,
+    ) -> Result<Self, crate::card::Error> {
+        // Newer data may only provide `regionRefs`.
+        let region_ref = match db_card.region_refs.first() {
+            Some(region_ref) if db_card.region_ref.is_empty() => region_ref,
+            _ => &db_card.region_ref,
+        };
 
-    fn try_from(db_card: &'a db::Card) -> Result<Self, Self::Error> {
         Ok(Card {
             associated_cards: db_card
                 .associated_card_refs
@@ -58,7 +70,7 @@ impl<'a> TryFrom<&'a db::Card> for Card<'a> {
                 .map(|s| CardCode::from_str(s.as_str()))
                 .collect::<Result<Vec<CardCode>, crate::card::Error>>()?,
             assets: db_card.assets.iter().map(Asset::from).collect(),
-            region: Region::from_str(&db_card.region_ref)?,
+            region: region_ref.parsed(Region::from_str)?,
             attack: db_card.attack,
             cost: db_card.cost,
             health: db_card.health,
@@ -71,24 +83,31 @@ impl<'a> TryFrom<&'a db::Card> for Card<'a> {
             keywords: db_card
                 .keyword_refs
                 .iter()
-                .map(AsRef::as_ref)
-                .map(Keyword::from)
+                .map(|keyword| Keyword::from_symbol(keyword, globals))
                 .collect(),
-            spell_speed: SpellSpeed::from_str(&db_card.spell_speed_ref)?,
-            rarity: Rarity::from_str(&db_card.rarity_ref)?,
-            supertype: Supertype::from_str(&db_card.supertype)?,
-            subtype: Subtype::from_str(&db_card.subtype)?,
-            r#type: Type::from_str(&db_card.r#type)?,
+            spell_speed: db_card.spell_speed_ref.parsed(SpellSpeed::from_str)?,
+            rarity: db_card.rarity_ref.parsed(Rarity::from_str)?,
+            supertype: db_card.supertype_ref.parsed(Supertype::from_str)?,
+            subtype: db_card.subtype_ref.parsed(Subtype::from_str)?,
+            r#type: db_card.type_ref.parsed(Type::from_str)?,
             collectible: db_card.collectible,
         })
     }
 }
 
+impl<'a> TryFrom<&'a db::Card> for Card<'a> {
+    type Error = crate::card::Error;
+
+    fn try_from(db_card: &'a db::Card) -> Result<Self, Self::Error> {
+        Card::try_from_db(db_card, &crate::db().globals)
+    }
+}
+
 impl<'a> TryFrom<&'a str> for Card<'a> {
     type Error = crate::card::Error;
 
     fn try_from(card_code: &'a str) -> Result<Self, Self::Error> {
-        let db_card = DB
+        let db_card = crate::db()
             .collection
             .0
             .iter()
@@ -98,30 +117,28 @@ impl<'a> TryFrom<&'a str> for Card<'a> {
     }
 }
 
-lazy_static! {
-    static ref REGION_TO_INT: HashMap<&'static str, u32> = {
-        let mut map = HashMap::new();
-        map.insert("DE", 0);
-        map.insert("FR", 1);
-        map.insert("IO", 2);
-        map.insert("NX", 3);
-        map.insert("PZ", 4);
-        map.insert("SI", 5);
-        map
-    };
-    static ref INT_TO_REGION: HashMap<u32, &'static str> = {
-        let mut map = HashMap::new();
-        map.insert(0, "DE");
-        map.insert(1, "FR");
-        map.insert(2, "IO");
-        map.insert(3, "NX");
-        map.insert(4, "PZ");
-        map.insert(5, "SI");
-        map
-    };
-}
+static REGION_TO_INT: Lazy<HashMap<&'static str, u32>> = Lazy::new(|| {
+    let mut map = HashMap::new();
+    map.insert("DE", 0);
+    map.insert("FR", 1);
+    map.insert("IO", 2);
+    map.insert("NX", 3);
+    map.insert("PZ", 4);
+    map.insert("SI", 5);
+    map
+});
+static INT_TO_REGION: Lazy<HashMap<u32, &'static str>> = Lazy::new(|| {
+    let mut map = HashMap::new();
+    map.insert(0, "DE");
+    map.insert(1, "FR");
+    map.insert(2, "IO");
+    map.insert(3, "NX");
+    map.insert(4, "PZ");
+    map.insert(5, "SI");
+    map
+});
 
-#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, EnumString, ToString)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, EnumString, ToString)]
 pub enum Region {
     #[strum(default = "true")]
     Unknown(String),
@@ -138,6 +155,35 @@ pub enum Region {
 }
 
 impl Region {
+    /// The region's `nameRef` in Data Dragon.
+    pub fn name_ref(&self) -> &str {
+        match self {
+            Region::Unknown(name_ref) => name_ref,
+            Region::Neutral => "Neutral",
+            Region::Demacia => "Demacia",
+            Region::Freljord => "Freljord",
+            Region::Ionia => "Ionia",
+            Region::Noxus => "Noxus",
+            Region::PiltoverZaun => "PiltoverZaun",
+            Region::ShadowIsles => "ShadowIsles",
+        }
+    }
+
+    /// The region's localized name, abbreviation and icon in `globals`.
+    pub fn details<'g>(&self, globals: &'g db::Globals) -> Option<&'g db::Region> {
+        globals.region(self.name_ref())
+    }
+
+    /// The localized name of the region in the installed database.
+    pub fn name(&self) -> Option<&'static str> {
+        Some(&self.details(&crate::db().globals)?.name)
+    }
+
+    /// URL of the region's icon in the installed database.
+    pub fn icon_path(&self) -> Option<&'static str> {
+        Some(&self.details(&crate::db().globals)?.icon_absolute_path)
+    }
+
     fn from_value(value: u32) -> Region {
         match value {
             0 => Region::Demacia,
@@ -164,10 +210,11 @@ impl Region {
     }
 }
 
+/// URLs of a card's art, `AssetResolver` maps them to the files of an unpacked bundle.
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Asset<'a> {
-    game_absolute_path: &'a str,
-    full_absolute_path: &'a str,
+    pub game_absolute_path: &'a str,
+    pub full_absolute_path: &'a str,
 }
 
 impl<'a> From<&'a db::Asset> for Asset<'a> {
@@ -191,24 +238,27 @@ impl FromStr for CardCode {
     type Err = crate::card::Error;
 
     fn from_str(code: &str) -> Result<Self, Self::Err> {
+        // `<set><region><number>`, tokens and skills followed by e.g. `T1` or `T10`.
+        if code.len() < 7 || !code.is_ascii() {
+            return Err(Error::InvalidCardCode {
+                code: code.to_string(),
+            });
+        }
         let faction = REGION_TO_INT.get(&code[2..4]).unwrap_or(&99);
-        let assoc = {
-            if code.len() == 7 {
-                "".to_string()
-            } else {
-                (&code[7..9]).to_string()
-            }
-        };
         Ok(CardCode {
             set: (&code[0..2]).parse()?,
             faction: Region::from_value(*faction),
             number: (&code[4..7]).parse()?,
-            assoc,
+            assoc: code[7..].to_string(),
         })
     }
 }
 
 impl CardCode {
+    pub fn set(&self) -> u8 {
+        self.set
+    }
+
     pub fn to_code(&self) -> String {
         format!(
             "{:02}{}{}{}",
@@ -220,7 +270,7 @@ impl CardCode {
     }
 }
 
-#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, EnumString, ToString)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, EnumString, ToString)]
 pub enum KeywordType {
     #[strum(default = "true")]
     Unknown(String),
@@ -268,24 +318,46 @@ pub struct Keyword<'a> {
     description: &'a str,
 }
 
-impl<'a> From<&'a str> for Keyword<'a> {
-    fn from(db_keyword: &'a str) -> Self {
-        match DB
-            .globals
-            .keywords
-            .iter()
-            .find(|&x| x.name_ref == db_keyword)
-        {
+impl<'a> Keyword<'a> {
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    pub fn name_ref(&self) -> &KeywordType {
+        &self.name_ref
+    }
+
+    pub fn description(&self) -> &'a str {
+        self.description
+    }
+
+    /// Looks up the localized name and description of a keyword ref in `globals`.
+    pub fn from_globals(db_keyword: &'a str, globals: &'a db::Globals) -> Self {
+        Keyword::with_name_ref(
+            db_keyword,
+            KeywordType::from_str(db_keyword).unwrap(),
+            globals,
+        )
+    }
+
+    /// Like [`Keyword::from_globals`], with the keyword type parsed once per interned symbol.
+    fn from_symbol(db_keyword: &'a db::Symbol, globals: &'a db::Globals) -> Self {
+        let name_ref = db_keyword.parsed(KeywordType::from_str).unwrap();
+        Keyword::with_name_ref(db_keyword, name_ref, globals)
+    }
+
+    fn with_name_ref(db_keyword: &'a str, name_ref: KeywordType, globals: &'a db::Globals) -> Self {
+        match globals.keyword(db_keyword) {
             Some(keyword_details) => Keyword {
                 name: &keyword_details.name,
-                name_ref: KeywordType::from_str(&keyword_details.name_ref).unwrap(),
+                name_ref,
                 description: &keyword_details.description,
             },
             None => {
                 let description = "Keyword not implemented yet";
                 Keyword {
                     name: db_keyword,
-                    name_ref: KeywordType::from_str(db_keyword).unwrap(),
+                    name_ref,
                     description,
                 }
             }
@@ -293,7 +365,13 @@ impl<'a> From<&'a str> for Keyword<'a> {
     }
 }
 
-#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, EnumString, ToString)]
+impl<'a> From<&'a str> for Keyword<'a> {
+    fn from(db_keyword: &'a str) -> Self {
+        Keyword::from_globals(db_keyword, &crate::db().globals)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, EnumString, ToString)]
 pub enum SpellSpeed {
     #[strum(default = "true")]
     Unknown(String),
@@ -302,7 +380,7 @@ pub enum SpellSpeed {
     Burst,
 }
 
-#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, EnumString, ToString)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, EnumString, ToString)]
 pub enum Rarity {
     #[strum(default = "true")]
     Unknown(String),
@@ -313,7 +391,31 @@ pub enum Rarity {
     None,
 }
 
-#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, EnumString, ToString)]
+impl Rarity {
+    /// The rarity's `nameRef` in Data Dragon.
+    pub fn name_ref(&self) -> &str {
+        match self {
+            Rarity::Unknown(name_ref) => name_ref,
+            Rarity::Common => "Common",
+            Rarity::Rare => "Rare",
+            Rarity::Epic => "Epic",
+            Rarity::Champion => "Champion",
+            Rarity::None => "None",
+        }
+    }
+
+    /// The rarity's localized name in `globals`.
+    pub fn details<'g>(&self, globals: &'g db::Globals) -> Option<&'g db::Rarity> {
+        globals.rarity(self.name_ref())
+    }
+
+    /// The localized name of the rarity in the installed database.
+    pub fn name(&self) -> Option<&'static str> {
+        Some(&self.details(&crate::db().globals)?.name)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, EnumString, ToString)]
 pub enum Type {
     #[strum(default = "true")]
     Unknown(String),
@@ -323,7 +425,7 @@ pub enum Type {
     Unit,
 }
 
-#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, EnumString, ToString)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, EnumString, ToString)]
 pub enum Subtype {
     #[strum(default = "true")]
     Unknown(String),
@@ -337,7 +439,7 @@ pub enum Subtype {
     Yeti,
 }
 
-#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, EnumString, ToString)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, EnumString, ToString)]
 pub enum Supertype {
     #[strum(default = "true")]
     Unknown(String),
@@ -0,0 +1,160 @@
+use crate::{Rarity, Region, SpellSpeed, Supertype, Type};
+use runeterra_database::db::{self, Db};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// A consistency problem found in a card of a database.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Problem {
+    pub card_code: String,
+    pub kind: ProblemKind,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ProblemKind {
+    /// The card code isn't `<set><region><number>` optionally followed by `T<number>`.
+    MalformedCardCode,
+    /// An `associatedCardRefs` code that isn't in the database.
+    UnknownAssociatedCard(String),
+    /// A `keywordRefs` entry that isn't in the globals.
+    UnknownKeyword(String),
+    /// A region ref that doesn't map to a [`Region`].
+    UnknownRegion(String),
+    /// A rarity ref that doesn't map to a [`Rarity`].
+    UnknownRarity(String),
+    /// A spell speed ref that doesn't map to a [`SpellSpeed`].
+    UnknownSpellSpeed(String),
+    /// A collectible champion without a champion unit among its associated cards.
+    MissingLevelUp,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: ", self.card_code)?;
+        match &self.kind {
+            ProblemKind::MalformedCardCode => write!(f, "malformed card code"),
+            ProblemKind::UnknownAssociatedCard(code) => {
+                write!(f, "unknown associated card {}", code)
+            }
+            ProblemKind::UnknownKeyword(keyword) => write!(f, "unknown keyword {:?}", keyword),
+            ProblemKind::UnknownRegion(region) => write!(f, "unknown region {:?}", region),
+            ProblemKind::UnknownRarity(rarity) => write!(f, "unknown rarity {:?}", rarity),
+            ProblemKind::UnknownSpellSpeed(spell_speed) => {
+                write!(f, "unknown spell speed {:?}", spell_speed)
+            }
+            ProblemKind::MissingLevelUp => write!(f, "champion has no level-up form"),
+        }
+    }
+}
+
+/// Every problem found by [`check`], in collection order.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct CheckReport(pub Vec<Problem>);
+
+impl CheckReport {
+    pub fn is_ok(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Display for CheckReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for problem in &self.0 {
+            writeln!(f, "{}", problem)?;
+        }
+        Ok(())
+    }
+}
+
+/// Validates a loaded database, reporting every problem instead of stopping at the first one.
+pub fn check(db: &Db) -> CheckReport {
+    // Loading already rejects duplicate card codes.
+    let cards: HashMap<&str, &db::Card> = db
+        .collection
+        .0
+        .iter()
+        .map(|card| (card.card_code.as_str(), card))
+        .collect();
+
+    let mut problems = Vec::new();
+    for card in &db.collection.0 {
+        let mut report = |kind| {
+            problems.push(Problem {
+                card_code: card.card_code.to_string(),
+                kind,
+            })
+        };
+
+        if !is_well_formed(&card.card_code) {
+            report(ProblemKind::MalformedCardCode);
+        }
+        for code in &card.associated_card_refs {
+            if !cards.contains_key(code.as_ref()) {
+                report(ProblemKind::UnknownAssociatedCard(code.to_string()));
+            }
+        }
+        for keyword in &card.keyword_refs {
+            if db.globals.keyword(keyword).is_none() {
+                report(ProblemKind::UnknownKeyword(keyword.to_string()));
+            }
+        }
+
+        let region_refs = Some(&card.region_ref)
+            .filter(|region_ref| !region_ref.is_empty())
+            .into_iter()
+            .chain(&card.region_refs);
+        for region_ref in region_refs {
+            if let Ok(Region::Unknown(_)) | Err(_) = region_ref.parsed(Region::from_str) {
+                report(ProblemKind::UnknownRegion(region_ref.to_string()));
+            }
+        }
+        if let Ok(Rarity::Unknown(_)) | Err(_) = card.rarity_ref.parsed(Rarity::from_str) {
+            report(ProblemKind::UnknownRarity(card.rarity_ref.to_string()));
+        }
+        // Only spells have a spell speed.
+        if !card.spell_speed_ref.is_empty() {
+            if let Ok(SpellSpeed::Unknown(_)) | Err(_) =
+                card.spell_speed_ref.parsed(SpellSpeed::from_str)
+            {
+                report(ProblemKind::UnknownSpellSpeed(
+                    card.spell_speed_ref.to_string(),
+                ));
+            }
+        }
+
+        if card.collectible && is_champion(card) {
+            let levels_up = card.associated_card_refs.iter().any(|code| {
+                cards.get(code.as_ref()).is_some_and(|associated| {
+                    is_champion(associated)
+                        && associated.type_ref.parsed(Type::from_str) == Ok(Type::Unit)
+                })
+            });
+            if !levels_up {
+                report(ProblemKind::MissingLevelUp);
+            }
+        }
+    }
+
+    CheckReport(problems)
+}
+
+fn is_champion(card: &db::Card) -> bool {
+    card.supertype_ref.parsed(Supertype::from_str) == Ok(Supertype::Champion)
+}
+
+fn is_well_formed(card_code: &str) -> bool {
+    let bytes = card_code.as_bytes();
+    if bytes.len() < 7 {
+        return false;
+    }
+    let (code, token) = bytes.split_at(7);
+    code[..2].iter().all(u8::is_ascii_digit)
+        && code[2..4].iter().all(u8::is_ascii_uppercase)
+        && code[4..].iter().all(u8::is_ascii_digit)
+        && match token.split_first() {
+            None => true,
+            Some((b'T', number)) => !number.is_empty() && number.iter().all(u8::is_ascii_digit),
+            Some(_) => false,
+        }
+}
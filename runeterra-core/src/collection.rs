@@ -1,5 +1,5 @@
-use crate::{Card, DB};
-use std::convert::TryFrom;
+use crate::{Card, Region, Supertype, Type};
+use runeterra_database::db::Db;
 
 #[derive(Debug, Default)]
 pub struct Collection<'a> {
@@ -8,13 +8,55 @@ pub struct Collection<'a> {
 
 impl<'a> Collection<'a> {
     pub fn new() -> Self {
-        let cards: Vec<Card> = DB
+        Collection::from_db(crate::db())
+    }
+
+    /// Builds the collection from an explicitly loaded database, e.g. one in another locale.
+    ///
+    /// Panics if a card can't be converted, see [`Collection::try_from_db`] for the fallible
+    /// version.
+    pub fn from_db(db: &'a Db) -> Self {
+        Collection::try_from_db(db).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Builds the collection from an explicitly loaded database, failing on the first card that
+    /// can't be converted.
+    pub fn try_from_db(db: &'a Db) -> Result<Self, crate::card::Error> {
+        let cards = db
             .collection
             .0
             .iter()
-            .map(|db_card| Card::try_from(db_card).expect("Card does not exist in database"))
-            .collect();
+            .map(|db_card| Card::try_from_db(db_card, &db.globals))
+            .collect::<Result<Vec<Card>, _>>()?;
+
+        Ok(Collection { cards })
+    }
+
+    /// Cards that can be added to a deck, skipping tokens, skills and champion level-ups.
+    pub fn collectible(&self) -> impl Iterator<Item = &Card<'a>> {
+        self.cards.iter().filter(|card| card.collectible)
+    }
+
+    /// Collectible champion cards, without their level-up forms.
+    pub fn champions(&self) -> impl Iterator<Item = &Card<'a>> {
+        self.collectible()
+            .filter(|card| card.supertype == Supertype::Champion)
+    }
+
+    /// All cards released in the given set.
+    pub fn by_set(&self, set: u8) -> impl Iterator<Item = &Card<'a>> {
+        self.cards
+            .iter()
+            .filter(move |card| card.card_code.set() == set)
+    }
+
+    /// All cards belonging to the given region.
+    pub fn by_region<'r>(&'r self, region: &'r Region) -> impl Iterator<Item = &'r Card<'a>> {
+        self.cards.iter().filter(move |card| card.region == *region)
+    }
 
-        Collection { cards }
+    /// All cards of the given type.
+    pub fn by_type<'r>(&'r self, r#type: &'r Type) -> impl Iterator<Item = &'r Card<'a>> {
+        self.cards.iter().filter(move |card| card.r#type == *r#type)
     }
 }
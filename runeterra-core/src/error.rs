@@ -1,6 +1,20 @@
+use thiserror::Error;
+
 #[derive(Debug)]
 pub enum Error {}
 
+/// Returned by [`crate::init`].
+#[derive(Debug, Error)]
+pub enum InitError {
+    #[error("Database is already initialized")]
+    AlreadyInitialized,
+    #[error("Invalid card in database")]
+    InvalidCard {
+        #[from]
+        source: crate::card::Error,
+    },
+}
+
 //impl fmt::Display for Error {
 //    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
 //        match *self {
@@ -1,18 +1,67 @@
 #[macro_use]
 extern crate strum_macros;
-#[macro_use]
-extern crate lazy_static;
 
 mod card;
+mod check;
 mod collection;
 mod deck;
 mod error;
 
 pub use self::card::*;
+pub use self::check::*;
 pub use self::collection::*;
 pub use self::deck::*;
 pub use self::error::*;
 
-lazy_static! {
-    pub static ref DB: runeterra_database::db::Db = { runeterra_database::db::Db::new() };
+use once_cell::sync::OnceCell;
+use runeterra_database::db::Db;
+use std::ops::Deref;
+
+static INSTALLED: OnceCell<Db> = OnceCell::new();
+static COLLECTION: OnceCell<Collection<'static>> = OnceCell::new();
+
+/// Installs the database used by conversions that don't take one explicitly, such as
+/// `Card::try_from(&str)` and `Collection::new`.
+///
+/// Must be called before the database is first used. The installed database never changes, to
+/// pick up new data while running keep a `LiveDb` and use `Collection::from_db` on its current
+/// database instead.
+///
+/// Nothing is installed if a card of `db` can't be converted.
+pub fn init(db: Db) -> Result<(), InitError> {
+    if INSTALLED.get().is_some() {
+        return Err(InitError::AlreadyInitialized);
+    }
+    Collection::try_from_db(&db)?;
+    INSTALLED.set(db).map_err(|_| InitError::AlreadyInitialized)
+}
+
+/// The installed database, loading the embedded one on first use if [`init`] was never called.
+///
+/// Panics if the embedded data is invalid.
+pub fn db() -> &'static Db {
+    INSTALLED.get_or_init(Db::new)
+}
+
+/// Every card of the installed database, converted once on first use.
+///
+/// Prefer it over converting cards one by one with `Card::try_from`.
+pub fn collection() -> &'static Collection<'static> {
+    COLLECTION.get_or_init(Collection::new)
+}
+
+/// The installed database, dereferencing to [`db()`].
+#[deprecated(note = "use `db()` instead")]
+pub static DB: InstalledDb = InstalledDb;
+
+/// Type of the deprecated [`DB`] static, kept so code using it still compiles.
+#[derive(Debug)]
+pub struct InstalledDb;
+
+impl Deref for InstalledDb {
+    type Target = Db;
+
+    fn deref(&self) -> &Db {
+        db()
+    }
 }
@@ -1,10 +1,198 @@
-use runeterra_core::Collection;
-
-
-
+use runeterra_core::{
+    check, CardCode, CheckReport, Collection, KeywordType, ProblemKind, Rarity, Region, Supertype,
+    Type,
+};
+use runeterra_database::db::{self, Db, Locale};
 
 #[test]
 fn parse_database() {
     let collection = Collection::new();
     dbg!(collection.cards);
 }
+
+#[test]
+fn collection_views() {
+    let collection = Collection::new();
+
+    assert!(collection.collectible().count() < collection.cards.len());
+    assert!(collection.collectible().all(|card| card.collectible));
+    assert!(collection
+        .collectible()
+        .all(|card| card.r#type != Type::Ability));
+
+    assert_eq!(collection.champions().count(), 24);
+    assert!(collection
+        .champions()
+        .all(|card| card.supertype == Supertype::Champion && card.collectible));
+
+    assert_eq!(collection.by_set(1).count(), collection.cards.len());
+    assert_eq!(collection.by_set(2).count(), 0);
+
+    let ionia = Region::Ionia;
+    assert!(collection
+        .by_region(&ionia)
+        .all(|card| card.region == ionia));
+    assert_ne!(collection.by_region(&ionia).count(), 0);
+
+    let spell = Type::Spell;
+    assert!(collection.by_type(&spell).all(|card| card.r#type == spell));
+    assert_ne!(collection.by_type(&spell).count(), 0);
+}
+
+#[test]
+fn collection_from_db() {
+    let db = Db::with_locale(Locale::EnUs);
+    let collection = Collection::from_db(&db);
+    assert_eq!(collection.cards.len(), db.collection.0.len());
+
+    let index = db
+        .collection
+        .0
+        .iter()
+        .position(|card| card.card_code == "01NX020T3")
+        .unwrap();
+    let card = &collection.cards[index];
+    assert_eq!(card.name, "Draven");
+    assert_eq!(card.keywords[0].name_ref(), &KeywordType::QuickStrike);
+    assert_eq!(card.keywords[0].name(), "Quick Attack");
+}
+
+#[test]
+fn localized_types() {
+    // Only en_us is embedded, so fake a Spanish bundle by translating the type names.
+    let data = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../runeterra-database/resources/en_us/data");
+    let translate = |json: String| {
+        json.replace(r#""supertype": "Champion""#, r#""supertype": "Campeón""#)
+            .replace(r#""type": "Unit""#, r#""type": "Unidad""#)
+    };
+    let set1 = translate(std::fs::read_to_string(data.join("set1-en_us.json")).unwrap());
+    // A card missing from the English data, translated with the names of the others.
+    let mut cards: Vec<serde_json::Value> = serde_json::from_str(&set1).unwrap();
+    let mut card = cards.swap_remove(
+        cards
+            .iter()
+            .position(|card| card["supertype"] == "Campeón")
+            .unwrap(),
+    );
+    card["cardCode"] = "02IO001".into();
+    card["collectible"] = true.into();
+
+    let dir = std::env::temp_dir().join(format!("runeterra-core-es_es-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::copy(
+        data.join("globals-en_us.json"),
+        dir.join("globals-es_es.json"),
+    )
+    .unwrap();
+    std::fs::write(dir.join("set1-es_es.json"), set1).unwrap();
+    std::fs::write(
+        dir.join("set2-es_es.json"),
+        serde_json::to_vec(&vec![card]).unwrap(),
+    )
+    .unwrap();
+    let options = db::LoadOptions {
+        locale: Locale::EsEs,
+        ..db::LoadOptions::default()
+    };
+    let db = Db::from_dir_with_options(&dir, options);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let db = db.unwrap();
+    let collection = Collection::from_db(&db);
+    assert_eq!(collection.champions().count(), 25);
+    assert_eq!(collection.by_set(2).next().unwrap().r#type, Type::Unit);
+    assert_eq!(
+        collection.by_type(&Type::Unit).count(),
+        runeterra_core::collection().by_type(&Type::Unit).count() + 1
+    );
+}
+
+#[test]
+fn card_codes() {
+    assert_eq!("01IO012".parse::<CardCode>().unwrap().set(), 1);
+    assert!("01IO012T10".parse::<CardCode>().is_ok());
+    for code in &["", "01IO", "01IO01", "01IO0é2"] {
+        assert!(code.parse::<CardCode>().is_err(), "{}", code);
+    }
+}
+
+#[test]
+fn collection_is_converted_once() {
+    let collection = runeterra_core::collection();
+    assert!(std::ptr::eq(collection, runeterra_core::collection()));
+    assert_eq!(
+        collection.cards.len(),
+        runeterra_core::db().collection.0.len()
+    );
+}
+
+#[test]
+fn region_and_rarity_details() {
+    assert_eq!(Region::PiltoverZaun.name(), Some("Piltover & Zaun"));
+    assert!(Region::Noxus
+        .icon_path()
+        .unwrap()
+        .ends_with("icon-noxus.png"));
+    assert_eq!(Region::Unknown("Bilgewater".to_string()).name(), None);
+    assert_eq!(Rarity::Champion.name(), Some("Champion"));
+}
+
+#[test]
+fn consistency_check() {
+    let mut db = Db::new();
+    assert_eq!(check(&db), CheckReport::default());
+
+    let cards = &mut db.collection.0;
+    let lucian = cards
+        .iter()
+        .position(|card| card.card_code == "01DE022")
+        .unwrap();
+    cards[lucian]
+        .associated_card_refs
+        .retain(|code| code != "01DE022T1");
+    cards[lucian].associated_card_refs.push("01DE999".into());
+    cards[lucian].keyword_refs.push("Lifelink".into());
+    cards[lucian].rarity_ref = "Legendary".into();
+    cards.push(db::Card {
+        card_code: "1DE022".into(),
+        region_ref: "Targon".into(),
+        spell_speed_ref: "Instant".into(),
+        rarity_ref: "Common".into(),
+        ..Default::default()
+    });
+
+    let report = check(&db);
+    let problems = report
+        .0
+        .iter()
+        .map(|problem| (problem.card_code.as_str(), &problem.kind))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        problems,
+        vec![
+            (
+                "01DE022",
+                &ProblemKind::UnknownAssociatedCard("01DE999".to_string())
+            ),
+            (
+                "01DE022",
+                &ProblemKind::UnknownKeyword("Lifelink".to_string())
+            ),
+            (
+                "01DE022",
+                &ProblemKind::UnknownRarity("Legendary".to_string())
+            ),
+            ("01DE022", &ProblemKind::MissingLevelUp),
+            ("1DE022", &ProblemKind::MalformedCardCode),
+            ("1DE022", &ProblemKind::UnknownRegion("Targon".to_string())),
+            (
+                "1DE022",
+                &ProblemKind::UnknownSpellSpeed("Instant".to_string())
+            ),
+        ]
+    );
+    assert!(report
+        .to_string()
+        .contains("01DE022: champion has no level-up form"));
+}
@@ -0,0 +1,20 @@
+use runeterra_core::{Collection, InitError};
+use runeterra_database::db::{Db, Locale};
+
+#[test]
+fn explicit_init() {
+    let db = Db::load_with_locale(Locale::EnUs).unwrap();
+    let cards = db.collection.0.len();
+
+    runeterra_core::init(db).unwrap();
+    assert!(matches!(
+        runeterra_core::init(Db::new()),
+        Err(InitError::AlreadyInitialized)
+    ));
+
+    assert_eq!(runeterra_core::db().collection.0.len(), cards);
+    #[allow(deprecated)]
+    let installed = runeterra_core::DB.collection.0.len();
+    assert_eq!(installed, cards);
+    assert_eq!(Collection::new().cards.len(), cards);
+}
@@ -0,0 +1,17 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use runeterra_database::db::Db;
+
+fn load(c: &mut Criterion) {
+    let snapshot = Db::new().to_snapshot();
+
+    let mut group = c.benchmark_group("load");
+    group.sample_size(20);
+    group.bench_function("json", |b| b.iter_with_large_drop(|| Db::load().unwrap()));
+    group.bench_function("snapshot", |b| {
+        b.iter_with_large_drop(|| Db::from_snapshot(&snapshot).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, load);
+criterion_main!(benches);
@@ -0,0 +1,28 @@
+use std::env;
+use std::path::Path;
+
+/// Locales with a cargo feature, embedded when their `resources/<locale>/` folder exists.
+const LOCALES: &[&str] = &[
+    "de_de", "en_us", "es_es", "es_mx", "fr_fr", "it_it", "ja_jp", "ko_kr", "pl_pl", "pt_br",
+    "ru_ru", "th_th", "tr_tr", "vi_vn", "zh_tw",
+];
+
+fn main() {
+    println!("cargo:rerun-if-changed=resources");
+    let resources = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("resources");
+    for locale in LOCALES {
+        println!("cargo:rustc-check-cfg=cfg(embed_{})", locale);
+        let feature = format!("CARGO_FEATURE_{}", locale.to_uppercase());
+        if env::var_os(feature).is_none() {
+            continue;
+        }
+        if resources.join(locale).is_dir() {
+            println!("cargo:rustc-cfg=embed_{}", locale);
+        } else {
+            println!(
+                "cargo:warning=feature `{}` is enabled but resources/{}/ is missing, the locale isn't embedded",
+                locale, locale
+            );
+        }
+    }
+}
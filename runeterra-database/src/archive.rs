@@ -0,0 +1,157 @@
+use crate::bundle::BundleFiles;
+use crate::database::{Db, LoadOptions};
+use crate::error::DbError;
+use crate::locale::Locale;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use zip::ZipArchive;
+
+fn open(path: &Path) -> io::Result<ZipArchive<File>> {
+    ZipArchive::new(File::open(path)?).map_err(io::Error::from)
+}
+
+fn zip_error(path: &Path, source: zip::result::ZipError) -> DbError {
+    match source {
+        zip::result::ZipError::Io(source) => DbError::io(path.display().to_string(), source),
+        source => DbError::Zip {
+            path: path.display().to_string(),
+            source,
+        },
+    }
+}
+
+/// Largest entry decompressed, well above the data files and card art of a bundle.
+const MAX_ENTRY_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Decompresses an entry, failing past [`MAX_ENTRY_SIZE`] whatever size its header claims.
+fn read_entry(entry: impl Read) -> io::Result<Vec<u8>> {
+    let mut contents = Vec::new();
+    entry.take(MAX_ENTRY_SIZE + 1).read_to_end(&mut contents)?;
+    if contents.len() as u64 > MAX_ENTRY_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("entry larger than {} bytes", MAX_ENTRY_SIZE),
+        ));
+    }
+    Ok(contents)
+}
+
+fn file_name(entry_name: &str) -> &str {
+    entry_name.rsplit('/').next().unwrap_or(entry_name)
+}
+
+impl Db {
+    /// Loads Data Dragon bundles straight from their zip archives, in the default locale.
+    ///
+    /// See [`Db::from_zips_with_locale`].
+    pub fn from_zips<P: AsRef<Path>>(paths: impl IntoIterator<Item = P>) -> Result<Self, DbError> {
+        Db::from_zips_with_locale(paths, Locale::default())
+    }
+
+    /// Loads Data Dragon bundles, e.g. `core-en_us.zip` and `set1-lite-en_us.zip`, straight from
+    /// their zip archives.
+    ///
+    /// Behaves like [`Db::from_dir_with_locale`] with each archive unpacked side by side.
+    pub fn from_zips_with_locale<P: AsRef<Path>>(
+        paths: impl IntoIterator<Item = P>,
+        locale: Locale,
+    ) -> Result<Self, DbError> {
+        Db::from_zips_with_options(paths, locale.into())
+    }
+
+    /// Like [`Db::from_zips_with_locale`], with further loading options.
+    pub fn from_zips_with_options<P: AsRef<Path>>(
+        paths: impl IntoIterator<Item = P>,
+        options: LoadOptions,
+    ) -> Result<Self, DbError> {
+        let locale = options.locale;
+        let mut files = BundleFiles::default();
+
+        for path in paths {
+            let path = path.as_ref();
+            let file =
+                File::open(path).map_err(|err| DbError::io(path.display().to_string(), err))?;
+            let mut archive = ZipArchive::new(file).map_err(|err| zip_error(path, err))?;
+            for index in 0..archive.len() {
+                let entry = archive
+                    .by_index(index)
+                    .map_err(|err| zip_error(path, err))?;
+                let file_name = file_name(entry.name()).to_string();
+                if entry.is_file() && BundleFiles::wants(&file_name, locale) {
+                    let name = format!("{}/{}", path.display(), entry.name());
+                    let contents = read_entry(entry).map_err(|err| DbError::io(&*name, err))?;
+                    files.insert(&file_name, name, locale, contents);
+                }
+            }
+        }
+
+        Db::from_bundle_files(options, files)
+    }
+}
+
+/// Index of the card and region images inside Data Dragon zip archives, keyed by locale and file
+/// name, e.g. `en_us` and `01IO012T2-full.png`.
+///
+/// The locale is the folder of the image in its archive, `en_us/img/cards/01IO012T2.png`, so the
+/// same card from archives of different locales doesn't collide. Images outside a locale folder
+/// aren't indexed.
+#[derive(Debug, Default)]
+pub struct ZipImages {
+    entries: HashMap<(Locale, String), (PathBuf, String)>,
+}
+
+impl ZipImages {
+    /// Lists the `.png` entries of every archive without decompressing them.
+    pub fn index<P: AsRef<Path>>(paths: impl IntoIterator<Item = P>) -> io::Result<Self> {
+        let mut entries = HashMap::new();
+
+        for path in paths {
+            let archive = open(path.as_ref())?;
+            for name in archive.file_names() {
+                if !name.ends_with(".png") {
+                    continue;
+                }
+                let locale = name.split('/').find_map(|folder| folder.parse().ok());
+                if let Some(locale) = locale {
+                    entries.insert(
+                        (locale, file_name(name).to_string()),
+                        (path.as_ref().to_path_buf(), name.to_string()),
+                    );
+                }
+            }
+        }
+
+        Ok(ZipImages { entries })
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn contains(&self, locale: Locale, file_name: &str) -> bool {
+        self.entries.contains_key(&(locale, file_name.to_string()))
+    }
+
+    /// Decompresses the image of `locale` named `file_name`.
+    pub fn read(&self, locale: Locale, file_name: &str) -> io::Result<Vec<u8>> {
+        let (path, name) = self
+            .entries
+            .get(&(locale, file_name.to_string()))
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("{}/{} not found in archives", locale, file_name),
+                )
+            })?;
+
+        let mut archive = open(path)?;
+        let entry = archive.by_name(name)?;
+        read_entry(entry)
+    }
+}
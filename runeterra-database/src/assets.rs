@@ -0,0 +1,140 @@
+use crate::database::{Card, Db, Region};
+use crate::error::DbError;
+use crate::locale::Locale;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Maps card art and region icons to the image files of unpacked Data Dragon bundles.
+///
+/// Images are looked up by the file name of their URL in the data, so bundles can be unpacked
+/// side by side or their `img` folders copied into one directory.
+#[derive(Debug, Default)]
+pub struct AssetResolver {
+    images: HashMap<String, PathBuf>,
+}
+
+/// The local image files of a card, `None` where the bundle lacks the image.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CardImages<'r> {
+    pub game: Option<&'r Path>,
+    pub full: Option<&'r Path>,
+    /// Alternative art, named `<card code>-alt...png` in the bundles.
+    pub alternates: Vec<&'r Path>,
+}
+
+/// An image referenced by the data but missing from the bundles.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MissingImage {
+    /// Card code of the card, or `nameRef` of the region the image belongs to.
+    pub owner: String,
+    pub url: String,
+}
+
+/// Calls `visit` with every `.png` file below `dir`, skipping the folders of other locales.
+///
+/// Links to folders aren't followed, so a link loop can't recurse forever.
+fn walk(dir: &Path, locale: Locale, visit: &mut dyn FnMut(&str, PathBuf)) -> Result<(), DbError> {
+    let io_error = |err| DbError::io(dir.display().to_string(), err);
+
+    for entry in fs::read_dir(dir).map_err(io_error)? {
+        let entry = entry.map_err(io_error)?;
+        let path = entry.path();
+        let file_name = match path.file_name().and_then(|name| name.to_str()) {
+            Some(file_name) => file_name.to_string(),
+            None => continue,
+        };
+
+        if entry.file_type().map_err(io_error)?.is_dir() {
+            match file_name.parse::<Locale>() {
+                Ok(other) if other != locale => {}
+                _ => walk(&path, locale, visit)?,
+            }
+        } else if file_name.ends_with(".png") {
+            visit(&file_name, path);
+        }
+    }
+
+    Ok(())
+}
+
+fn file_name(url: &str) -> &str {
+    url.rsplit('/').next().unwrap_or(url)
+}
+
+impl AssetResolver {
+    /// Indexes the images of `locale` below `dir` without reading them.
+    pub fn index(dir: impl AsRef<Path>, locale: Locale) -> Result<Self, DbError> {
+        let dir = dir.as_ref();
+        if !dir.is_dir() {
+            return Err(DbError::MissingFile(dir.display().to_string()));
+        }
+
+        let mut images = HashMap::new();
+        walk(dir, locale, &mut |file_name, path| {
+            images.insert(file_name.to_string(), path);
+        })?;
+        Ok(AssetResolver { images })
+    }
+
+    pub fn len(&self) -> usize {
+        self.images.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.images.is_empty()
+    }
+
+    /// The local file of an image URL from the data, e.g. `gameAbsolutePath`.
+    pub fn resolve(&self, url: &str) -> Option<&Path> {
+        self.images.get(file_name(url)).map(PathBuf::as_path)
+    }
+
+    pub fn card(&self, card: &Card) -> CardImages<'_> {
+        let asset = card.assets.first();
+        let prefix = format!("{}-alt", card.card_code);
+        let mut alternates = self
+            .images
+            .iter()
+            .filter(|(file_name, _)| file_name.starts_with(&prefix))
+            .map(|(_, path)| path.as_path())
+            .collect::<Vec<_>>();
+        alternates.sort();
+
+        CardImages {
+            game: asset.and_then(|asset| self.resolve(&asset.game_absolute_path)),
+            full: asset.and_then(|asset| self.resolve(&asset.full_absolute_path)),
+            alternates,
+        }
+    }
+
+    pub fn region_icon(&self, region: &Region) -> Option<&Path> {
+        self.resolve(&region.icon_absolute_path)
+    }
+
+    /// Every card image and region icon of `db` that isn't in the bundles.
+    pub fn missing(&self, db: &Db) -> Vec<MissingImage> {
+        let cards = db.collection.0.iter().flat_map(|card| {
+            card.assets.iter().flat_map(move |asset| {
+                vec![
+                    (card.card_code.as_str(), asset.game_absolute_path.as_str()),
+                    (card.card_code.as_str(), asset.full_absolute_path.as_str()),
+                ]
+            })
+        });
+        let regions = db
+            .globals
+            .regions()
+            .iter()
+            .map(|region| (region.name_ref.as_str(), region.icon_absolute_path.as_str()));
+
+        cards
+            .chain(regions)
+            .filter(|(_, url)| !url.is_empty() && self.resolve(url).is_none())
+            .map(|(owner, url)| MissingImage {
+                owner: owner.to_string(),
+                url: url.to_string(),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,200 @@
+use crate::database::{set_number, Card, Collection, Db, LoadOptions, Metadata};
+use crate::embedded;
+use crate::error::DbError;
+use crate::locale::Locale;
+use crate::schema::{SchemaMode, SchemaReport};
+use crate::snapshot::{self, Fnv1a};
+use crate::text;
+use serde::de::DeserializeOwned;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::hash::Hasher;
+
+/// A data file and the name used to refer to it in errors.
+#[derive(Debug)]
+pub(crate) struct BundleFile {
+    name: String,
+    contents: Cow<'static, [u8]>,
+}
+
+impl BundleFile {
+    fn value(&self) -> Result<serde_json::Value, DbError> {
+        self.parse()
+    }
+
+    fn parse<T: DeserializeOwned>(&self) -> Result<T, DbError> {
+        let parse = || serde_json::from_slice(&self.contents);
+        let result = match self.contents {
+            // Embedded data in release builds, `Text` can borrow from it.
+            Cow::Borrowed(contents) => text::borrowing(contents, parse),
+            Cow::Owned(_) => parse(),
+        };
+        result.map_err(|err| DbError::json(&*self.name, None, err))
+    }
+
+    /// Parses a set file, naming the offending card on failure.
+    fn parse_set(&self) -> Result<Collection, DbError> {
+        self.parse().map_err(|err| {
+            let err = match err {
+                DbError::Json { source, .. } => source,
+                err => return err,
+            };
+            // Only reached on failure, so re-parsing card by card to find the culprit is cheap
+            // enough. Syntax errors leave the card unknown.
+            let card_code = serde_json::from_slice::<Vec<serde_json::Value>>(&self.contents)
+                .ok()
+                .and_then(|cards| {
+                    cards
+                        .into_iter()
+                        .find(|card| serde_json::from_value::<Card>(card.clone()).is_err())
+                })
+                .and_then(|card| card.get("cardCode")?.as_str().map(str::to_string));
+            DbError::json(&*self.name, card_code, err)
+        })
+    }
+}
+
+/// Raw Data Dragon files of one locale, gathered from directories or archives.
+#[derive(Debug, Default)]
+pub(crate) struct BundleFiles {
+    globals: Option<BundleFile>,
+    sets: BTreeMap<u8, BundleFile>,
+    metadata: Vec<BundleFile>,
+}
+
+impl BundleFiles {
+    /// Whether `file_name` is a data file of `locale` that [`BundleFiles::insert`] would keep.
+    pub(crate) fn wants(file_name: &str, locale: Locale) -> bool {
+        file_name == format!("globals-{}.json", locale)
+            || file_name == "metadata.json"
+            || set_number(file_name, locale.as_str()).is_some()
+    }
+
+    /// Keeps the contents of `file_name` if it is a data file of `locale`.
+    ///
+    /// `name` is the full path of the file, used in errors.
+    pub(crate) fn insert(
+        &mut self,
+        file_name: &str,
+        name: impl Into<String>,
+        locale: Locale,
+        contents: impl Into<Cow<'static, [u8]>>,
+    ) {
+        let file = BundleFile {
+            name: name.into(),
+            contents: contents.into(),
+        };
+
+        if file_name == format!("globals-{}.json", locale) {
+            self.globals = Some(file);
+        } else if file_name == "metadata.json" {
+            self.metadata.push(file);
+        } else if let Some(set) = set_number(file_name, locale.as_str()) {
+            self.sets.insert(set, file);
+        }
+    }
+}
+
+impl BundleFiles {
+    /// Hash of everything that influences the parsed database, used as snapshot cache key.
+    fn hash(&self, options: &LoadOptions) -> u64 {
+        let mut hasher = Fnv1a::default();
+        hasher.write(env!("CARGO_PKG_VERSION").as_bytes());
+        hasher.write(options.locale.as_str().as_bytes());
+        hasher.write(&[options.schema as u8]);
+
+        for file in self.globals.iter().chain(self.sets.values()) {
+            hasher.write(&(file.contents.len() as u64).to_le_bytes());
+            hasher.write(&file.contents);
+        }
+        for set in self.sets.keys() {
+            hasher.write(&[*set]);
+        }
+        for file in &self.metadata {
+            hasher.write(&(file.contents.len() as u64).to_le_bytes());
+            hasher.write(&file.contents);
+        }
+        hasher.finish()
+    }
+}
+
+impl Db {
+    /// Parses `files`, falling back to the embedded globals and sets of `options.locale` for
+    /// anything they do not contain.
+    pub(crate) fn from_bundle_files(
+        options: LoadOptions,
+        mut files: BundleFiles,
+    ) -> Result<Self, DbError> {
+        let locale = options.locale;
+
+        for path in embedded::iter(locale) {
+            let file_name = path.rsplit('/').next().unwrap_or(&path).to_string();
+            let missing = match set_number(&file_name, locale.as_str()) {
+                Some(set) => !files.sets.contains_key(&set),
+                None => files.globals.is_none() && file_name == format!("globals-{}.json", locale),
+            };
+            if missing {
+                let contents = embedded::get(locale, &path)
+                    .ok_or_else(|| DbError::MissingFile(path.to_string()))?;
+                files.insert(&file_name, path, locale, contents);
+            }
+        }
+
+        let cache_path = options
+            .cache_dir
+            .as_ref()
+            .map(|cache_dir| snapshot::cache_path(cache_dir, locale, files.hash(&options)));
+        if let Some(db) = cache_path.as_deref().and_then(snapshot::read_cache) {
+            return Ok(db);
+        }
+
+        let db = text::interning(|| Db::parse_bundle_files(&options, files))?;
+        if let Some(cache_path) = cache_path {
+            snapshot::write_cache(&cache_path, &db);
+        }
+        Ok(db)
+    }
+
+    fn parse_bundle_files(options: &LoadOptions, files: BundleFiles) -> Result<Self, DbError> {
+        let locale = options.locale;
+        let mut report = SchemaReport::default();
+
+        let globals_file = files
+            .globals
+            .ok_or_else(|| DbError::MissingFile(format!("globals-{}.json", locale)))?;
+        let globals = globals_file.parse()?;
+        let check = options.schema != SchemaMode::Tolerant;
+        if check {
+            report.check_globals(&globals_file.name, &globals_file.value()?);
+        }
+
+        if files.sets.is_empty() {
+            return Err(DbError::MissingFile(format!("set1-{}.json", locale)));
+        }
+        let mut collection = Collection::default();
+        for (set, file) in files.sets {
+            collection.extend_set(set, file.parse_set()?)?;
+            if check {
+                report.check_set(&file.name, &file.value()?);
+            }
+        }
+        collection.resolve_type_refs(locale);
+        if options.schema == SchemaMode::Strict && !report.is_empty() {
+            return Err(DbError::Schema(report));
+        }
+
+        let metadata = files
+            .metadata
+            .iter()
+            .map(BundleFile::parse::<Metadata>)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Db {
+            locale,
+            globals,
+            collection,
+            metadata,
+            schema_report: report,
+        })
+    }
+}
@@ -1,10 +1,25 @@
-use rust_embed::RustEmbed;
+use crate::bundle::BundleFiles;
+use crate::embedded;
+use crate::error::DbError;
+use crate::locale::Locale;
+use crate::schema::{SchemaMode, SchemaReport};
+use crate::text::{Symbol, Text};
+use once_cell::sync::{Lazy, OnceCell};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use thiserror::Error;
 
 #[derive(Debug)]
 pub struct Db {
+    pub locale: Locale,
     pub globals: Globals,
     pub collection: Collection,
+    /// Contents of the `metadata.json` files of the bundles loaded from disk.
+    pub metadata: Vec<Metadata>,
+    /// Unknown and missing fields of the data files when loaded in [`SchemaMode::Report`], empty
+    /// otherwise.
+    pub schema_report: SchemaReport,
 }
 
 impl Default for Db {
@@ -13,13 +28,168 @@ impl Default for Db {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(rename_all = "camelCase")]
+/// Options shared by every way of loading a [`Db`].
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct LoadOptions {
+    pub locale: Locale,
+    pub schema: SchemaMode,
+    /// Directory to cache parsed databases in, as snapshots keyed by a hash of the data files.
+    ///
+    /// Loading the same data again then skips JSON parsing, see [`Db::from_snapshot`].
+    pub cache_dir: Option<PathBuf>,
+}
+
+impl From<Locale> for LoadOptions {
+    fn from(locale: Locale) -> Self {
+        LoadOptions {
+            locale,
+            ..LoadOptions::default()
+        }
+    }
+}
+
+/// Regions, keywords, spell speeds and rarities, along with their display text.
+///
+/// Lookups are indexed on first use. The entries can only be changed through the `*_mut`
+/// accessors, which drop the index so the next lookup sees the changes.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct Globals {
-    pub regions: Vec<Region>,
-    pub keywords: Vec<Keyword>,
-    pub spell_speeds: Vec<SpellSpeed>,
-    pub rarities: Vec<Rarity>,
+    regions: Vec<Region>,
+    keywords: Vec<Keyword>,
+    spell_speeds: Vec<SpellSpeed>,
+    rarities: Vec<Rarity>,
+    sets: Vec<Set>,
+    formats: Vec<Format>,
+    vocab_terms: Vec<VocabTerm>,
+    #[serde(skip)]
+    index: OnceCell<GlobalsIndex>,
+}
+
+/// Positions of the globals entries by their lookup keys, built on first lookup.
+#[derive(Debug, Default)]
+struct GlobalsIndex {
+    regions: HashMap<String, usize>,
+    region_abbreviations: HashMap<String, usize>,
+    keywords: HashMap<String, usize>,
+    spell_speeds: HashMap<String, usize>,
+    rarities: HashMap<String, usize>,
+}
+
+fn positions<'a, T: 'a>(
+    entries: &'a [T],
+    key: impl Fn(&'a T) -> &'a str,
+) -> HashMap<String, usize> {
+    entries
+        .iter()
+        .enumerate()
+        .map(|(position, entry)| (key(entry).to_string(), position))
+        .collect()
+}
+
+macro_rules! globals_accessors {
+    ($($(#[$doc:meta])* $field:ident, $field_mut:ident: $ty:ty;)*) => {
+        $(
+            $(#[$doc])*
+            pub fn $field(&self) -> &[$ty] {
+                &self.$field
+            }
+
+            $(#[$doc])*
+            pub fn $field_mut(&mut self) -> &mut Vec<$ty> {
+                self.index.take();
+                &mut self.$field
+            }
+        )*
+    };
+}
+
+impl Globals {
+    globals_accessors! {
+        regions, regions_mut: Region;
+        keywords, keywords_mut: Keyword;
+        spell_speeds, spell_speeds_mut: SpellSpeed;
+        rarities, rarities_mut: Rarity;
+        /// Only present in newer data.
+        sets, sets_mut: Set;
+        /// Only present in newer data.
+        formats, formats_mut: Format;
+        /// Only present in newer data.
+        vocab_terms, vocab_terms_mut: VocabTerm;
+    }
+
+    fn index(&self) -> &GlobalsIndex {
+        self.index.get_or_init(|| GlobalsIndex {
+            regions: positions(&self.regions, |region| &region.name_ref),
+            region_abbreviations: positions(&self.regions, |region| &region.abbreviation),
+            keywords: positions(&self.keywords, |keyword| &keyword.name_ref),
+            spell_speeds: positions(&self.spell_speeds, |spell_speed| &spell_speed.name_ref),
+            rarities: positions(&self.rarities, |rarity| &rarity.name_ref),
+        })
+    }
+
+    /// Looks up a region by its `nameRef`, e.g. `PiltoverZaun`.
+    pub fn region(&self, name_ref: &str) -> Option<&Region> {
+        Some(&self.regions[*self.index().regions.get(name_ref)?])
+    }
+
+    /// Looks up a region by the abbreviation used in card codes, e.g. `PZ`.
+    pub fn region_by_abbreviation(&self, abbreviation: &str) -> Option<&Region> {
+        Some(&self.regions[*self.index().region_abbreviations.get(abbreviation)?])
+    }
+
+    /// Looks up a keyword by its `nameRef`, e.g. `QuickStrike`.
+    pub fn keyword(&self, name_ref: &str) -> Option<&Keyword> {
+        Some(&self.keywords[*self.index().keywords.get(name_ref)?])
+    }
+
+    /// Looks up a spell speed by its `nameRef`.
+    pub fn spell_speed(&self, name_ref: &str) -> Option<&SpellSpeed> {
+        Some(&self.spell_speeds[*self.index().spell_speeds.get(name_ref)?])
+    }
+
+    /// Looks up a rarity by its `nameRef`.
+    pub fn rarity(&self, name_ref: &str) -> Option<&Rarity> {
+        Some(&self.rarities[*self.index().rarities.get(name_ref)?])
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Metadata {
+    pub locales: Vec<String>,
+    #[serde(default)]
+    pub client_hash: Option<String>,
+    #[serde(default)]
+    pub gameplay_data_hash: Option<String>,
+    #[serde(default)]
+    pub timestamp: Option<String>,
+    #[serde(default)]
+    pub patchline_ref: Option<String>,
+}
+
+#[derive(Debug, Default, Eq, PartialEq, Ord, PartialOrd, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Set {
+    pub icon_absolute_path: String,
+    pub name: String,
+    pub name_ref: String,
+}
+
+#[derive(Debug, Default, Eq, PartialEq, Ord, PartialOrd, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Format {
+    pub icon_absolute_path: String,
+    pub name: String,
+    pub name_ref: String,
+}
+
+#[derive(Debug, Default, Eq, PartialEq, Ord, PartialOrd, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct VocabTerm {
+    pub description: String,
+    pub name: String,
+    pub name_ref: String,
 }
 
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Deserialize, Serialize)]
@@ -53,63 +223,246 @@ pub struct Rarity {
     pub name_ref: String,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
 pub struct Collection(pub Vec<Card>);
 
-#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Deserialize, Serialize)]
-#[serde(rename_all = "camelCase")]
+/// A card code that appears more than once across the loaded sets.
+#[derive(Debug, Error)]
+#[error("Duplicate card code {card_code} in set {set}")]
+pub struct DuplicateCardCode {
+    pub card_code: String,
+    pub set: u8,
+}
+
+impl Collection {
+    /// Appends the cards of `set`, tagging each card with its set number.
+    ///
+    /// Fails without modifying the collection if any card code is already present.
+    pub fn extend_set(&mut self, set: u8, cards: Collection) -> Result<(), DuplicateCardCode> {
+        let mut codes: HashSet<&str> = self.0.iter().map(|card| card.card_code.as_str()).collect();
+        if let Some(card) = cards
+            .0
+            .iter()
+            .find(|card| !codes.insert(card.card_code.as_str()))
+        {
+            return Err(DuplicateCardCode {
+                card_code: card.card_code.to_string(),
+                set,
+            });
+        }
+
+        self.0.extend(cards.0.into_iter().map(|mut card| {
+            card.set = set;
+            card
+        }));
+        Ok(())
+    }
+
+    /// Fills the `*_ref` fields with the English type names of the cards.
+    ///
+    /// Cards missing from the English data are translated with the names of the cards found in
+    /// both.
+    pub(crate) fn resolve_type_refs(&mut self, locale: Locale) {
+        let mut resolved = vec![false; self.0.len()];
+        for (card, resolved) in self.0.iter_mut().zip(&mut resolved) {
+            match ENGLISH_TYPE_NAMES.get(card.card_code.as_str()) {
+                Some(english) if locale != Locale::EnUs => {
+                    card.type_ref = Symbol::from(&*english.r#type);
+                    card.supertype_ref = Symbol::from(&*english.supertype);
+                    card.subtype_ref = Symbol::from(&*english.subtype);
+                    *resolved = true;
+                }
+                _ => {}
+            }
+        }
+
+        let translations = self.type_ref_translations(|position| resolved[position]);
+        for (card, _) in self
+            .0
+            .iter_mut()
+            .zip(&resolved)
+            .filter(|(_, resolved)| !**resolved)
+        {
+            card.translate_type_refs(&translations);
+        }
+    }
+
+    /// Localized type names mapped to the English ones of the cards at positions kept by
+    /// `filter`.
+    pub(crate) fn type_ref_translations(
+        &self,
+        filter: impl Fn(usize) -> bool,
+    ) -> HashMap<String, Symbol> {
+        let mut translations = HashMap::new();
+        for (_, card) in self
+            .0
+            .iter()
+            .enumerate()
+            .filter(|(position, _)| filter(*position))
+        {
+            for (name, english) in card.type_names().iter().zip(&card.type_refs()) {
+                translations
+                    .entry(name.to_string())
+                    .or_insert_with(|| (*english).clone());
+            }
+        }
+        translations
+    }
+}
+
+impl Card {
+    /// Fills the `*_ref` fields from `translations`, keeping the names without one.
+    pub(crate) fn translate_type_refs(&mut self, translations: &HashMap<String, Symbol>) {
+        let translate = |name: &Symbol| translations.get(name.as_str()).unwrap_or(name).clone();
+        self.type_ref = translate(&self.r#type);
+        self.supertype_ref = translate(&self.supertype);
+        self.subtype_ref = translate(&self.subtype);
+    }
+
+    fn type_names(&self) -> [&Symbol; 3] {
+        [&self.r#type, &self.supertype, &self.subtype]
+    }
+
+    fn type_refs(&self) -> [&Symbol; 3] {
+        [&self.type_ref, &self.supertype_ref, &self.subtype_ref]
+    }
+}
+
+/// The type fields of a card in the embedded English data.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct TypeNames {
+    #[serde(rename = "cardCode")]
+    card_code: String,
+    #[serde(rename = "type")]
+    r#type: String,
+    supertype: String,
+    subtype: String,
+}
+
+/// English type names by card code, empty when `en_us` isn't embedded.
+static ENGLISH_TYPE_NAMES: Lazy<HashMap<String, TypeNames>> = Lazy::new(|| {
+    let locale = Locale::EnUs;
+    embedded::iter(locale)
+        .iter()
+        .filter(|path| set_number(path, locale.as_str()).is_some())
+        .filter_map(|path| embedded::get(locale, path))
+        .filter_map(|contents| serde_json::from_slice::<Vec<TypeNames>>(&contents).ok())
+        .flatten()
+        .map(|names| (names.card_code.clone(), names))
+        .collect()
+});
+
+/// A card as found in a set file.
+///
+/// Missing fields are defaulted so newer or older data still loads, use [`SchemaMode::Report`]
+/// or [`SchemaMode::Strict`] to find out about them.
+#[derive(Debug, Default, Eq, PartialEq, Ord, PartialOrd, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct Card {
-    pub associated_cards: Vec<String>,
-    pub associated_card_refs: Vec<String>,
+    pub associated_cards: Vec<Text>,
+    pub associated_card_refs: Vec<Text>,
     pub assets: Vec<Asset>,
-    pub region: String,
-    pub region_ref: String,
+    pub region: Symbol,
+    pub region_ref: Symbol,
     pub attack: u8,
     pub cost: u8,
     pub health: u8,
-    pub description: String,
-    pub description_raw: String,
-    pub levelup_description: String,
-    pub levelup_description_raw: String,
-    pub flavor_text: String,
-    pub artist_name: String,
-    pub name: String,
-    pub card_code: String,
-    pub keywords: Vec<String>,
-    pub keyword_refs: Vec<String>,
-    pub spell_speed: String,
-    pub spell_speed_ref: String,
-    pub rarity: String,
-    pub rarity_ref: String,
-    pub subtype: String,
-    pub supertype: String,
-    pub r#type: String,
+    pub description: Text,
+    pub description_raw: Text,
+    pub levelup_description: Text,
+    pub levelup_description_raw: Text,
+    pub flavor_text: Text,
+    pub artist_name: Symbol,
+    pub name: Text,
+    pub card_code: Text,
+    pub keywords: Vec<Symbol>,
+    pub keyword_refs: Vec<Symbol>,
+    pub spell_speed: Symbol,
+    pub spell_speed_ref: Symbol,
+    pub rarity: Symbol,
+    pub rarity_ref: Symbol,
+    pub subtype: Symbol,
+    pub supertype: Symbol,
+    pub r#type: Symbol,
+    /// Locale-independent [`subtype`](Card::subtype), see [`Card::type_ref`].
+    pub subtype_ref: Symbol,
+    /// Locale-independent [`supertype`](Card::supertype), see [`Card::type_ref`].
+    pub supertype_ref: Symbol,
+    /// Locale-independent [`type`](Card::type), not part of Data Dragon.
+    ///
+    /// Filled on load with the English name from the embedded `en_us` data, falling back to the
+    /// localized name when that data isn't embedded or doesn't know the card's type.
+    pub type_ref: Symbol,
     pub collectible: bool,
+    /// Only present in newer data, which allows cards to belong to several regions.
+    pub regions: Vec<Symbol>,
+    /// Only present in newer data.
+    pub region_refs: Vec<Symbol>,
+    /// Only present in newer data.
+    pub subtypes: Vec<Symbol>,
+    /// Only present in newer data.
+    pub formats: Vec<Symbol>,
+    /// Only present in newer data.
+    pub format_refs: Vec<Symbol>,
+    /// Set reference such as `Set1`, only present in newer data.
+    #[serde(rename = "set")]
+    pub set_ref: Symbol,
+    /// Set number, taken from the name of the file the card was loaded from.
+    #[serde(skip)]
+    pub set: u8,
 }
 
-#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Deserialize, Serialize)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Default, Eq, PartialEq, Ord, PartialOrd, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct Asset {
-    pub game_absolute_path: String,
-    pub full_absolute_path: String,
+    pub game_absolute_path: Text,
+    pub full_absolute_path: Text,
 }
 
-#[derive(RustEmbed)]
-#[folder = "$CARGO_MANIFEST_DIR/resources/en_us/"]
-struct DatabaseAsset;
+/// Returns the set number of a `setN-<locale>.json` file, ignoring any leading directories.
+pub fn set_number(path: &str, locale: &str) -> Option<u8> {
+    let file_name = path.rsplit(['/', '\\']).next()?;
+    file_name
+        .strip_prefix("set")?
+        .strip_suffix(".json")?
+        .strip_suffix(locale)?
+        .strip_suffix('-')?
+        .parse()
+        .ok()
+}
 
 impl Db {
+    /// Loads the embedded data for the default locale.
+    ///
+    /// Panics if the data is invalid, see [`Db::load`] for the fallible version.
     pub fn new() -> Self {
-        let file =
-            DatabaseAsset::get("data/globals-en_us.json").expect("Invalid globals.json file path");
-        let globals = serde_json::from_slice(&file).expect("Invalid globals.json format");
+        Db::with_locale(Locale::default())
+    }
 
-        let file = DatabaseAsset::get("data/set1-en_us.json").expect("Invalid set1.json file path");
-        let collection = serde_json::from_slice(&file).expect("Invalid set1.json format");
+    /// Loads the embedded data for `locale`.
+    ///
+    /// Panics if the locale was not embedded or its data is invalid, see
+    /// [`Db::load_with_locale`] for the fallible version.
+    pub fn with_locale(locale: Locale) -> Self {
+        Db::load_with_locale(locale).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Loads the embedded data for the default locale.
+    pub fn load() -> Result<Self, DbError> {
+        Db::load_with_locale(Locale::default())
+    }
+
+    /// Loads the embedded data for `locale`.
+    pub fn load_with_locale(locale: Locale) -> Result<Self, DbError> {
+        Db::load_with_options(locale.into())
+    }
 
-        Db {
-            globals,
-            collection,
+    /// Loads the embedded data for `options.locale`.
+    pub fn load_with_options(options: LoadOptions) -> Result<Self, DbError> {
+        if !options.locale.is_embedded() {
+            return Err(DbError::LocaleNotEmbedded(options.locale));
         }
+        Db::from_bundle_files(options, BundleFiles::default())
     }
 }
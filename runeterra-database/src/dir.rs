@@ -0,0 +1,106 @@
+use crate::bundle::BundleFiles;
+use crate::database::{Db, LoadOptions};
+use crate::error::DbError;
+use crate::locale::Locale;
+use crate::snapshot::Fnv1a;
+use std::fs;
+use std::hash::Hasher;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+/// Calls `visit` with the file name and path of every data file of `locale` below `dir`.
+///
+/// Links to folders aren't followed, so a link loop can't recurse forever.
+fn walk(
+    dir: &Path,
+    locale: Locale,
+    visit: &mut dyn FnMut(&str, &Path) -> Result<(), DbError>,
+) -> Result<(), DbError> {
+    let io_error = |err| DbError::io(dir.display().to_string(), err);
+
+    for entry in fs::read_dir(dir).map_err(io_error)? {
+        let entry = entry.map_err(io_error)?;
+        let path = entry.path();
+        let file_name = match path.file_name().and_then(|name| name.to_str()) {
+            Some(file_name) => file_name,
+            None => continue,
+        };
+
+        if entry.file_type().map_err(io_error)?.is_dir() {
+            // Card art makes up almost all of a bundle, no need to walk it.
+            if file_name != "img" {
+                walk(&path, locale, visit)?;
+            }
+        } else if BundleFiles::wants(file_name, locale) {
+            visit(file_name, &path)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Hash of the paths, sizes and modification times of the data files of `locale` below `dir`.
+///
+/// Cheap to compute, used to notice new or changed files without reading them.
+pub(crate) fn fingerprint(dir: &Path, locale: Locale) -> Result<u64, DbError> {
+    let mut files = Vec::new();
+    walk(dir, locale, &mut |_, path| {
+        let name = path.display().to_string();
+        let metadata = fs::metadata(path).map_err(|err| DbError::io(&*name, err))?;
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .unwrap_or_default();
+        files.push((name, metadata.len(), modified));
+        Ok(())
+    })?;
+    files.sort();
+
+    let mut hasher = Fnv1a::default();
+    for (name, len, modified) in files {
+        hasher.write(name.as_bytes());
+        hasher.write_u64(len);
+        hasher.write_u128(modified.as_nanos());
+    }
+    Ok(hasher.finish())
+}
+
+impl Db {
+    /// Loads an unpacked Data Dragon core and set bundles from `path`, in the default locale.
+    ///
+    /// See [`Db::from_dir_with_locale`].
+    pub fn from_dir(path: impl AsRef<Path>) -> Result<Self, DbError> {
+        Db::from_dir_with_locale(path, Locale::default())
+    }
+
+    /// Loads an unpacked Data Dragon core and set bundles from `path`.
+    ///
+    /// `globals-<locale>.json`, `setN-<locale>.json` and `metadata.json` files are searched for
+    /// anywhere below `path`, so bundles can be unpacked side by side or their `data` folders
+    /// copied into one directory. Globals and sets missing on disk fall back to the embedded data
+    /// if `locale` is embedded.
+    pub fn from_dir_with_locale(path: impl AsRef<Path>, locale: Locale) -> Result<Self, DbError> {
+        Db::from_dir_with_options(path, locale.into())
+    }
+
+    /// Like [`Db::from_dir_with_locale`], with further loading options.
+    pub fn from_dir_with_options(
+        path: impl AsRef<Path>,
+        options: LoadOptions,
+    ) -> Result<Self, DbError> {
+        let path = path.as_ref();
+        if !path.is_dir() {
+            return Err(DbError::MissingFile(path.display().to_string()));
+        }
+
+        let mut files = BundleFiles::default();
+        walk(path, options.locale, &mut |file_name, path| {
+            let name = path.display().to_string();
+            let contents = fs::read(path).map_err(|err| DbError::io(&*name, err))?;
+            files.insert(file_name, name, options.locale, contents);
+            Ok(())
+        })?;
+        Db::from_bundle_files(options, files)
+    }
+}
@@ -0,0 +1,69 @@
+//! Data Dragon bundles embedded at compile time, one per enabled locale feature.
+//!
+//! The build script only sets `embed_<locale>` for features whose `resources/<locale>/` folder
+//! exists, so enabling a locale without its data warns instead of failing the build.
+
+use crate::locale::Locale;
+use std::borrow::Cow;
+
+macro_rules! embedded_locales {
+    ($($cfg:ident, $folder:literal => $asset:ident, $locale:ident;)*) => {
+        $(
+            #[cfg($cfg)]
+            #[derive(rust_embed::RustEmbed)]
+            #[folder = $folder]
+            struct $asset;
+        )*
+
+        pub(crate) fn contains(locale: Locale) -> bool {
+            match locale {
+                $(
+                    #[cfg($cfg)]
+                    Locale::$locale => true,
+                )*
+                #[allow(unreachable_patterns)]
+                _ => false,
+            }
+        }
+
+        pub(crate) fn get(locale: Locale, path: &str) -> Option<Cow<'static, [u8]>> {
+            match locale {
+                $(
+                    #[cfg($cfg)]
+                    Locale::$locale => <$asset as rust_embed::RustEmbed>::get(path),
+                )*
+                #[allow(unreachable_patterns)]
+                _ => None,
+            }
+        }
+
+        pub(crate) fn iter(locale: Locale) -> Vec<Cow<'static, str>> {
+            match locale {
+                $(
+                    #[cfg($cfg)]
+                    Locale::$locale => <$asset as rust_embed::RustEmbed>::iter().collect(),
+                )*
+                #[allow(unreachable_patterns)]
+                _ => Vec::new(),
+            }
+        }
+    };
+}
+
+embedded_locales! {
+    embed_de_de, "$CARGO_MANIFEST_DIR/resources/de_de/" => DeDeAsset, DeDe;
+    embed_en_us, "$CARGO_MANIFEST_DIR/resources/en_us/" => EnUsAsset, EnUs;
+    embed_es_es, "$CARGO_MANIFEST_DIR/resources/es_es/" => EsEsAsset, EsEs;
+    embed_es_mx, "$CARGO_MANIFEST_DIR/resources/es_mx/" => EsMxAsset, EsMx;
+    embed_fr_fr, "$CARGO_MANIFEST_DIR/resources/fr_fr/" => FrFrAsset, FrFr;
+    embed_it_it, "$CARGO_MANIFEST_DIR/resources/it_it/" => ItItAsset, ItIt;
+    embed_ja_jp, "$CARGO_MANIFEST_DIR/resources/ja_jp/" => JaJpAsset, JaJp;
+    embed_ko_kr, "$CARGO_MANIFEST_DIR/resources/ko_kr/" => KoKrAsset, KoKr;
+    embed_pl_pl, "$CARGO_MANIFEST_DIR/resources/pl_pl/" => PlPlAsset, PlPl;
+    embed_pt_br, "$CARGO_MANIFEST_DIR/resources/pt_br/" => PtBrAsset, PtBr;
+    embed_ru_ru, "$CARGO_MANIFEST_DIR/resources/ru_ru/" => RuRuAsset, RuRu;
+    embed_th_th, "$CARGO_MANIFEST_DIR/resources/th_th/" => ThThAsset, ThTh;
+    embed_tr_tr, "$CARGO_MANIFEST_DIR/resources/tr_tr/" => TrTrAsset, TrTr;
+    embed_vi_vn, "$CARGO_MANIFEST_DIR/resources/vi_vn/" => ViVnAsset, ViVn;
+    embed_zh_tw, "$CARGO_MANIFEST_DIR/resources/zh_tw/" => ZhTwAsset, ZhTw;
+}
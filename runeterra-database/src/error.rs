@@ -0,0 +1,83 @@
+use crate::database::DuplicateCardCode;
+use crate::locale::Locale;
+use crate::schema::SchemaReport;
+use std::io;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum DbError {
+    #[error("Locale {0} is not embedded")]
+    LocaleNotEmbedded(Locale),
+    #[error("Missing data file {0}")]
+    MissingFile(String),
+    #[error("Failed to read {path}")]
+    Io {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+    #[cfg(feature = "zip")]
+    #[error("Invalid zip archive {path}")]
+    Zip {
+        path: String,
+        #[source]
+        source: zip::result::ZipError,
+    },
+    #[error(
+        "Invalid JSON in {file} at line {line}, column {column}{}",
+        parsing_card(card_code)
+    )]
+    Json {
+        file: String,
+        line: usize,
+        column: usize,
+        /// Code of the card being parsed, if the file is a set and the card could be located.
+        card_code: Option<String>,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error(transparent)]
+    DuplicateCardCode(#[from] DuplicateCardCode),
+    #[error("Invalid database snapshot")]
+    Snapshot(#[from] bincode::Error),
+    #[error("Database snapshot was written by an incompatible version")]
+    SnapshotVersion,
+    #[error("Overrides refer to unknown cards {unknown_cards:?} or fields {unknown_fields:?}")]
+    InvalidOverrides {
+        unknown_cards: Vec<String>,
+        /// Card code and field name of each unknown field, or field that can't be overridden.
+        unknown_fields: Vec<(String, String)>,
+    },
+    #[error("Data does not match the expected schema:\n{0}")]
+    Schema(SchemaReport),
+}
+
+fn parsing_card(card_code: &Option<String>) -> String {
+    match card_code {
+        Some(card_code) => format!(" while parsing card {}", card_code),
+        None => String::new(),
+    }
+}
+
+impl DbError {
+    pub(crate) fn io(path: impl Into<String>, source: io::Error) -> Self {
+        DbError::Io {
+            path: path.into(),
+            source,
+        }
+    }
+
+    pub(crate) fn json(
+        file: impl Into<String>,
+        card_code: Option<String>,
+        source: serde_json::Error,
+    ) -> Self {
+        DbError::Json {
+            file: file.into(),
+            line: source.line(),
+            column: source.column(),
+            card_code,
+            source,
+        }
+    }
+}
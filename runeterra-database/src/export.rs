@@ -0,0 +1,200 @@
+use crate::database::{Card, Db};
+use serde::Serialize;
+use std::io::{self, Write};
+
+/// A table of card data that can be exported.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ExportTable {
+    /// One row per card, see [`CardRow`].
+    Cards,
+    /// One row per keyword of each card, see [`KeywordRow`].
+    Keywords,
+    /// One row per associated card of each card, see [`AssociatedCardRow`].
+    AssociatedCards,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ExportFormat {
+    /// Comma separated values with a header row.
+    Csv,
+    /// One JSON object per line.
+    JsonLines,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CardRow<'a> {
+    pub code: &'a str,
+    pub name: &'a str,
+    pub region: &'a str,
+    pub cost: u8,
+    pub attack: u8,
+    pub health: u8,
+    pub r#type: &'a str,
+    pub rarity: &'a str,
+    pub set: u8,
+    /// Joined with `; ` in CSV.
+    pub keywords: Vec<&'a str>,
+    pub description: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct KeywordRow<'a> {
+    pub code: &'a str,
+    pub keyword: &'a str,
+    pub keyword_ref: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AssociatedCardRow<'a> {
+    pub code: &'a str,
+    pub associated_code: &'a str,
+}
+
+impl<'a> From<&'a Card> for CardRow<'a> {
+    fn from(card: &'a Card) -> Self {
+        CardRow {
+            code: &card.card_code,
+            name: &card.name,
+            region: &card.region,
+            cost: card.cost,
+            attack: card.attack,
+            health: card.health,
+            r#type: &card.r#type,
+            rarity: &card.rarity,
+            set: card.set,
+            keywords: card
+                .keywords
+                .iter()
+                .map(|keyword| keyword.as_str())
+                .collect(),
+            description: &card.description_raw,
+        }
+    }
+}
+
+impl CardRow<'_> {
+    const HEADER: [&'static str; 11] = [
+        "code",
+        "name",
+        "region",
+        "cost",
+        "attack",
+        "health",
+        "type",
+        "rarity",
+        "set",
+        "keywords",
+        "description",
+    ];
+
+    fn csv_record(&self) -> [String; 11] {
+        [
+            self.code.to_string(),
+            self.name.to_string(),
+            self.region.to_string(),
+            self.cost.to_string(),
+            self.attack.to_string(),
+            self.health.to_string(),
+            self.r#type.to_string(),
+            self.rarity.to_string(),
+            self.set.to_string(),
+            self.keywords.join("; "),
+            self.description.to_string(),
+        ]
+    }
+}
+
+impl Db {
+    pub fn card_rows(&self) -> impl Iterator<Item = CardRow<'_>> {
+        self.collection.0.iter().map(CardRow::from)
+    }
+
+    /// One row per entry of the longer of each card's `keywords` and `keywordRefs`, so none is
+    /// lost if they differ in length. A missing name is looked up in the globals by its ref, a
+    /// missing ref is left empty.
+    pub fn keyword_rows(&self) -> impl Iterator<Item = KeywordRow<'_>> {
+        self.collection.0.iter().flat_map(move |card| {
+            let len = card.keywords.len().max(card.keyword_refs.len());
+            (0..len).map(move |index| {
+                let keyword_ref = card
+                    .keyword_refs
+                    .get(index)
+                    .map_or("", |keyword_ref| keyword_ref.as_str());
+                let keyword = match card.keywords.get(index) {
+                    Some(keyword) => keyword.as_str(),
+                    None => self
+                        .globals
+                        .keyword(keyword_ref)
+                        .map_or("", |keyword| keyword.name.as_str()),
+                };
+                KeywordRow {
+                    code: &card.card_code,
+                    keyword,
+                    keyword_ref,
+                }
+            })
+        })
+    }
+
+    pub fn associated_card_rows(&self) -> impl Iterator<Item = AssociatedCardRow<'_>> {
+        self.collection.0.iter().flat_map(|card| {
+            card.associated_card_refs
+                .iter()
+                .map(move |associated_code| AssociatedCardRow {
+                    code: &card.card_code,
+                    associated_code,
+                })
+        })
+    }
+
+    /// Writes `table` to `writer` in `format`, the columns are the fields of the table's row type.
+    pub fn export(
+        &self,
+        table: ExportTable,
+        format: ExportFormat,
+        writer: impl Write,
+    ) -> io::Result<()> {
+        match (table, format) {
+            (ExportTable::Cards, ExportFormat::Csv) => {
+                let mut csv = csv::Writer::from_writer(writer);
+                csv.write_record(CardRow::HEADER)?;
+                for row in self.card_rows() {
+                    csv.write_record(row.csv_record())?;
+                }
+                csv.flush()
+            }
+            (ExportTable::Cards, ExportFormat::JsonLines) => {
+                write_json_lines(writer, self.card_rows())
+            }
+            (ExportTable::Keywords, ExportFormat::Csv) => write_csv(writer, self.keyword_rows()),
+            (ExportTable::Keywords, ExportFormat::JsonLines) => {
+                write_json_lines(writer, self.keyword_rows())
+            }
+            (ExportTable::AssociatedCards, ExportFormat::Csv) => {
+                write_csv(writer, self.associated_card_rows())
+            }
+            (ExportTable::AssociatedCards, ExportFormat::JsonLines) => {
+                write_json_lines(writer, self.associated_card_rows())
+            }
+        }
+    }
+}
+
+fn write_csv<T: Serialize>(writer: impl Write, rows: impl Iterator<Item = T>) -> io::Result<()> {
+    let mut csv = csv::Writer::from_writer(writer);
+    for row in rows {
+        csv.serialize(row)?;
+    }
+    csv.flush()
+}
+
+fn write_json_lines<T: Serialize>(
+    mut writer: impl Write,
+    rows: impl Iterator<Item = T>,
+) -> io::Result<()> {
+    for row in rows {
+        serde_json::to_writer(&mut writer, &row)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()
+}
@@ -1,5 +1,29 @@
+#[cfg(feature = "zip")]
+mod archive;
+mod assets;
+mod bundle;
 mod database;
+mod dir;
+mod embedded;
+mod error;
+mod export;
+mod live;
+mod locale;
+mod overrides;
+mod schema;
+mod snapshot;
+mod text;
 
 pub mod db {
+    #[cfg(feature = "zip")]
+    pub use crate::archive::*;
+    pub use crate::assets::*;
     pub use crate::database::*;
+    pub use crate::error::*;
+    pub use crate::export::*;
+    pub use crate::live::*;
+    pub use crate::locale::*;
+    pub use crate::overrides::*;
+    pub use crate::schema::*;
+    pub use crate::text::{Symbol, Text};
 }
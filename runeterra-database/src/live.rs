@@ -0,0 +1,146 @@
+use crate::database::{Db, LoadOptions};
+use crate::dir;
+use crate::error::DbError;
+use crate::overrides::Overrides;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread;
+use std::time::Duration;
+
+/// A database loaded from a directory, reloaded whenever its data files change.
+///
+/// Reloads happen on a background thread. A new database replaces the current one only once it
+/// loaded successfully, and databases handed out by [`LiveDb::current`] stay valid after being
+/// replaced, so cards borrowed from them by in-flight game state are unaffected.
+#[derive(Debug)]
+pub struct LiveDb {
+    shared: Arc<Shared>,
+    // Dropping the sender stops the background thread.
+    _stop: Sender<()>,
+}
+
+#[derive(Debug)]
+struct Shared {
+    dir: PathBuf,
+    options: LoadOptions,
+    overrides: Overrides,
+    current: RwLock<Arc<VersionedDb>>,
+    fingerprint: Mutex<u64>,
+    last_error: Mutex<Option<Arc<DbError>>>,
+}
+
+/// A database along with the number of times it was reloaded before being loaded.
+#[derive(Debug)]
+pub struct VersionedDb {
+    pub version: u64,
+    pub db: Db,
+}
+
+impl Shared {
+    fn load(&self) -> Result<Db, DbError> {
+        load(&self.dir, &self.options, &self.overrides)
+    }
+
+    /// Reloads the database if the data files changed since the last load.
+    fn reload(&self) -> Result<bool, Arc<DbError>> {
+        let result = self.try_reload().map_err(Arc::new);
+        *self.last_error.lock().unwrap() = result.as_ref().err().cloned();
+        result
+    }
+
+    fn try_reload(&self) -> Result<bool, DbError> {
+        let mut fingerprint = self.fingerprint.lock().unwrap();
+        let new_fingerprint = dir::fingerprint(&self.dir, self.options.locale)?;
+        if new_fingerprint == *fingerprint {
+            return Ok(false);
+        }
+
+        let db = self.load()?;
+        let mut current = self.current.write().unwrap();
+        *current = Arc::new(VersionedDb {
+            version: current.version + 1,
+            db,
+        });
+        *fingerprint = new_fingerprint;
+        Ok(true)
+    }
+}
+
+fn load(dir: &Path, options: &LoadOptions, overrides: &Overrides) -> Result<Db, DbError> {
+    let mut db = Db::from_dir_with_options(dir, options.clone())?;
+    db.apply_overrides(overrides)?;
+    Ok(db)
+}
+
+impl LiveDb {
+    /// Loads the database from `dir` like [`Db::from_dir_with_options`], then checks the data
+    /// files for changes every `interval`.
+    pub fn watch(
+        dir: impl AsRef<Path>,
+        options: LoadOptions,
+        interval: Duration,
+    ) -> Result<Self, DbError> {
+        LiveDb::watch_with_overrides(dir, options, Overrides::default(), interval)
+    }
+
+    /// Like [`LiveDb::watch`], applying `overrides` to the database on every load.
+    ///
+    /// A reload fails like any other if the overrides no longer apply, e.g. to a removed card.
+    pub fn watch_with_overrides(
+        dir: impl AsRef<Path>,
+        options: LoadOptions,
+        overrides: Overrides,
+        interval: Duration,
+    ) -> Result<Self, DbError> {
+        let dir = dir.as_ref().to_path_buf();
+        let fingerprint = dir::fingerprint(&dir, options.locale)?;
+        let db = load(&dir, &options, &overrides)?;
+
+        let shared = Arc::new(Shared {
+            dir,
+            options,
+            overrides,
+            current: RwLock::new(Arc::new(VersionedDb { version: 0, db })),
+            fingerprint: Mutex::new(fingerprint),
+            last_error: Mutex::new(None),
+        });
+
+        let (stop, stopped) = mpsc::channel::<()>();
+        {
+            let shared = shared.clone();
+            thread::spawn(move || {
+                while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                    // Failures are kept in `last_error`, the current database stays in use.
+                    let _ = shared.reload();
+                }
+            });
+        }
+
+        Ok(LiveDb {
+            shared,
+            _stop: stop,
+        })
+    }
+
+    /// The most recently loaded database.
+    pub fn current(&self) -> Arc<VersionedDb> {
+        self.shared.current.read().unwrap().clone()
+    }
+
+    /// Number of successful reloads so far.
+    pub fn version(&self) -> u64 {
+        self.shared.current.read().unwrap().version
+    }
+
+    /// Checks for changes right away instead of waiting for the background thread, returning
+    /// whether a new database was swapped in.
+    pub fn reload(&self) -> Result<bool, Arc<DbError>> {
+        self.shared.reload()
+    }
+
+    /// The error of the last reload attempt, if it failed.
+    pub fn last_error(&self) -> Option<Arc<DbError>> {
+        self.shared.last_error.lock().unwrap().clone()
+    }
+}
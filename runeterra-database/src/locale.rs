@@ -0,0 +1,103 @@
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// A Data Dragon locale.
+///
+/// Card codes and `*_ref` fields are identical across locales, only display text differs.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Locale {
+    DeDe,
+    #[default]
+    EnUs,
+    EsEs,
+    EsMx,
+    FrFr,
+    ItIt,
+    JaJp,
+    KoKr,
+    PlPl,
+    PtBr,
+    RuRu,
+    ThTh,
+    TrTr,
+    ViVn,
+    ZhTw,
+}
+
+#[derive(Debug, Error)]
+#[error("Unknown locale {0}")]
+pub struct UnknownLocale(pub String);
+
+impl Locale {
+    pub const ALL: [Locale; 15] = [
+        Locale::DeDe,
+        Locale::EnUs,
+        Locale::EsEs,
+        Locale::EsMx,
+        Locale::FrFr,
+        Locale::ItIt,
+        Locale::JaJp,
+        Locale::KoKr,
+        Locale::PlPl,
+        Locale::PtBr,
+        Locale::RuRu,
+        Locale::ThTh,
+        Locale::TrTr,
+        Locale::ViVn,
+        Locale::ZhTw,
+    ];
+
+    /// The locale as used in Data Dragon file and folder names, e.g. `en_us`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Locale::DeDe => "de_de",
+            Locale::EnUs => "en_us",
+            Locale::EsEs => "es_es",
+            Locale::EsMx => "es_mx",
+            Locale::FrFr => "fr_fr",
+            Locale::ItIt => "it_it",
+            Locale::JaJp => "ja_jp",
+            Locale::KoKr => "ko_kr",
+            Locale::PlPl => "pl_pl",
+            Locale::PtBr => "pt_br",
+            Locale::RuRu => "ru_ru",
+            Locale::ThTh => "th_th",
+            Locale::TrTr => "tr_tr",
+            Locale::ViVn => "vi_vn",
+            Locale::ZhTw => "zh_tw",
+        }
+    }
+
+    /// Whether the data for this locale was embedded at compile time, see the crate features.
+    pub fn is_embedded(self) -> bool {
+        crate::embedded::contains(self)
+    }
+
+    /// All locales embedded at compile time.
+    pub fn embedded() -> impl Iterator<Item = Locale> {
+        Locale::ALL
+            .iter()
+            .copied()
+            .filter(|locale| locale.is_embedded())
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = UnknownLocale;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lowercase = s.to_ascii_lowercase().replace('-', "_");
+        Locale::ALL
+            .iter()
+            .copied()
+            .find(|locale| locale.as_str() == lowercase)
+            .ok_or_else(|| UnknownLocale(s.to_string()))
+    }
+}
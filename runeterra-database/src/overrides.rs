@@ -0,0 +1,140 @@
+use crate::database::{Card, Db};
+use crate::error::DbError;
+use crate::schema;
+use crate::text::Symbol;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+/// Local fixes to card data, applied on top of a loaded [`Db`].
+///
+/// Read from a JSON object keyed by card code, whose values hold the fields to replace, named as
+/// in the set files:
+///
+/// ```json
+/// {
+///     "01IO012T2": { "keywordRefs": ["Burst"] }
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Overrides {
+    name: String,
+    cards: BTreeMap<String, Map<String, Value>>,
+}
+
+/// A field replaced by [`Db::apply_overrides`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Overridden {
+    pub card_code: String,
+    pub field: String,
+    pub previous: Value,
+    pub value: Value,
+}
+
+impl Overrides {
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, DbError> {
+        let name = path.as_ref().display().to_string();
+        let contents = fs::read(path).map_err(|err| DbError::io(&*name, err))?;
+        Overrides::from_slice(name, &contents)
+    }
+
+    /// Parses overrides, `name` is used to refer to them in errors.
+    pub fn from_slice(name: impl Into<String>, contents: &[u8]) -> Result<Self, DbError> {
+        let name = name.into();
+        let cards =
+            serde_json::from_slice(contents).map_err(|err| DbError::json(&*name, None, err))?;
+        Ok(Overrides { name, cards })
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+}
+
+/// Localized type fields and their locale-independent counterparts.
+const TYPE_FIELDS: &[(&str, &str)] = &[
+    ("type", "typeRef"),
+    ("supertype", "supertypeRef"),
+    ("subtype", "subtypeRef"),
+];
+
+impl Db {
+    /// Replaces card fields with the values in `overrides`, returning every replaced field.
+    ///
+    /// Nothing is applied unless every overridden card and field exists.
+    pub fn apply_overrides(&mut self, overrides: &Overrides) -> Result<Vec<Overridden>, DbError> {
+        let positions: HashMap<&str, usize> = self
+            .collection
+            .0
+            .iter()
+            .enumerate()
+            .map(|(position, card)| (card.card_code.as_str(), position))
+            .collect();
+
+        let mut unknown_cards = Vec::new();
+        let mut unknown_fields = Vec::new();
+        for (card_code, fields) in &overrides.cards {
+            if !positions.contains_key(card_code.as_str()) {
+                unknown_cards.push(card_code.clone());
+            }
+            // Codes are what overrides are keyed by, changing them could introduce duplicates.
+            let invalid = |field: &&String| !schema::is_card_field(field) || *field == "cardCode";
+            for field in fields.keys().filter(invalid) {
+                unknown_fields.push((card_code.clone(), field.clone()));
+            }
+        }
+        if !unknown_cards.is_empty() || !unknown_fields.is_empty() {
+            return Err(DbError::InvalidOverrides {
+                unknown_cards,
+                unknown_fields,
+            });
+        }
+
+        // Overridden type names are translated like the cards missing from the English data.
+        let translations = self.collection.type_ref_translations(|_| true);
+
+        // Patch every card before replacing any, so a bad value leaves the database untouched.
+        let mut patched = Vec::new();
+        let mut overridden = Vec::new();
+        for (card_code, fields) in &overrides.cards {
+            let position = positions[card_code.as_str()];
+            let card = &self.collection.0[position];
+            let mut value = serde_json::to_value(card).expect("Card is always serializable");
+
+            for (field, new) in fields {
+                let previous = value[field.as_str()].clone();
+                value[field.as_str()] = new.clone();
+                overridden.push(Overridden {
+                    card_code: card_code.clone(),
+                    field: field.clone(),
+                    previous,
+                    value: new.clone(),
+                });
+            }
+
+            for (field, field_ref) in TYPE_FIELDS {
+                if let (Some(Value::String(name)), false) =
+                    (fields.get(*field), fields.contains_key(*field_ref))
+                {
+                    let english = translations.get(name).map_or(name.as_str(), Symbol::as_str);
+                    value[*field_ref] = english.into();
+                }
+            }
+
+            let mut card_patched: Card = serde_json::from_value(value)
+                .map_err(|err| DbError::json(&*overrides.name, Some(card_code.clone()), err))?;
+            card_patched.set = card.set;
+            patched.push((position, card_patched));
+        }
+
+        for (position, card) in patched {
+            self.collection.0[position] = card;
+        }
+        Ok(overridden)
+    }
+}
@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// How to deal with data files that don't match the schema the `db` types were written for.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum SchemaMode {
+    /// Defaults missing fields and ignores unknown ones.
+    #[default]
+    Tolerant,
+    /// Like [`SchemaMode::Tolerant`], also listing the unknown and missing fields in the
+    /// [`schema_report`](crate::db::Db::schema_report) of the database.
+    ///
+    /// Checking the fields parses every file a second time.
+    Report,
+    /// Fails with a [`SchemaReport`] listing every unknown or missing field.
+    Strict,
+}
+
+/// Card fields of the original Data Dragon schema, expected in every card.
+const CARD_FIELDS: &[&str] = &[
+    "associatedCards",
+    "associatedCardRefs",
+    "assets",
+    "region",
+    "regionRef",
+    "attack",
+    "cost",
+    "health",
+    "description",
+    "descriptionRaw",
+    "levelupDescription",
+    "levelupDescriptionRaw",
+    "flavorText",
+    "artistName",
+    "name",
+    "cardCode",
+    "keywords",
+    "keywordRefs",
+    "spellSpeed",
+    "spellSpeedRef",
+    "rarity",
+    "rarityRef",
+    "subtype",
+    "supertype",
+    "type",
+    "collectible",
+];
+
+/// Card fields added by later releases, known but not expected.
+const NEWER_CARD_FIELDS: &[&str] = &[
+    "regions",
+    "regionRefs",
+    "subtypes",
+    "formats",
+    "formatRefs",
+    "set",
+];
+
+/// Card fields filled on load rather than read from Data Dragon, known so serialized cards load
+/// again.
+const DERIVED_CARD_FIELDS: &[&str] = &["subtypeRef", "supertypeRef", "typeRef"];
+
+/// Whether `field` is the JSON name of a [`crate::db::Card`] field.
+pub(crate) fn is_card_field(field: &str) -> bool {
+    CARD_FIELDS.contains(&field)
+        || NEWER_CARD_FIELDS.contains(&field)
+        || DERIVED_CARD_FIELDS.contains(&field)
+}
+
+const GLOBALS_FIELDS: &[&str] = &["regions", "keywords", "spellSpeeds", "rarities"];
+
+const NEWER_GLOBALS_FIELDS: &[&str] = &["sets", "formats", "vocabTerms"];
+
+fn is_globals_field(field: &str) -> bool {
+    GLOBALS_FIELDS.contains(&field) || NEWER_GLOBALS_FIELDS.contains(&field)
+}
+
+/// Unknown and missing fields of one card, or of a globals file.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct SchemaDrift {
+    pub file: String,
+    /// `None` for the top level fields of a globals file.
+    pub card_code: Option<String>,
+    pub unknown: Vec<String>,
+    pub missing: Vec<String>,
+}
+
+impl fmt::Display for SchemaDrift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.file)?;
+        if let Some(card_code) = &self.card_code {
+            write!(f, " card {}", card_code)?;
+        }
+        if !self.unknown.is_empty() {
+            write!(f, ": unknown {}", self.unknown.join(", "))?;
+        }
+        if !self.missing.is_empty() {
+            write!(f, ": missing {}", self.missing.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Every schema drift found while loading.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct SchemaReport(pub Vec<SchemaDrift>);
+
+impl SchemaReport {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn check_globals(&mut self, file: &str, globals: &Value) {
+        if let Some(globals) = globals.as_object() {
+            self.check(file, None, globals, GLOBALS_FIELDS, is_globals_field);
+        }
+    }
+
+    pub(crate) fn check_set(&mut self, file: &str, cards: &Value) {
+        for card in cards.as_array().into_iter().flatten() {
+            if let Some(card) = card.as_object() {
+                let card_code = card
+                    .get("cardCode")
+                    .and_then(Value::as_str)
+                    .map(str::to_string);
+                self.check(file, card_code, card, CARD_FIELDS, is_card_field);
+            }
+        }
+    }
+
+    fn check(
+        &mut self,
+        file: &str,
+        card_code: Option<String>,
+        object: &Map<String, Value>,
+        expected: &[&str],
+        is_known: fn(&str) -> bool,
+    ) {
+        let unknown: Vec<String> = object
+            .keys()
+            .filter(|key| !is_known(key))
+            .cloned()
+            .collect();
+        let missing: Vec<String> = expected
+            .iter()
+            .filter(|field| !object.contains_key(**field))
+            .map(|field| field.to_string())
+            .collect();
+
+        if !unknown.is_empty() || !missing.is_empty() {
+            self.0.push(SchemaDrift {
+                file: file.to_string(),
+                card_code,
+                unknown,
+                missing,
+            });
+        }
+    }
+}
+
+impl fmt::Display for SchemaReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for drift in &self.0 {
+            writeln!(f, "{}", drift)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,151 @@
+//! Compact binary snapshots of a parsed [`Db`], to skip JSON parsing on startup.
+
+use crate::database::{Card, Collection, Db, Globals, Metadata};
+use crate::error::DbError;
+use crate::locale::Locale;
+use crate::schema::SchemaReport;
+use crate::text;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::hash::Hasher;
+use std::path::{Path, PathBuf};
+
+/// Bumped whenever the layout of the `db` types changes.
+const FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+struct Header {
+    format_version: u32,
+    crate_version: String,
+    locale: String,
+}
+
+impl Header {
+    fn new(locale: Locale) -> Self {
+        Header {
+            format_version: FORMAT_VERSION,
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            locale: locale.to_string(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct SnapshotRef<'a> {
+    header: Header,
+    globals: &'a Globals,
+    cards: &'a [Card],
+    // Set numbers aren't serialized as part of the cards.
+    sets: Vec<u8>,
+    metadata: &'a [Metadata],
+    schema_report: &'a SchemaReport,
+}
+
+#[derive(Deserialize)]
+struct Snapshot {
+    // Checked on its own before decoding the rest.
+    _header: Header,
+    globals: Globals,
+    cards: Vec<Card>,
+    sets: Vec<u8>,
+    metadata: Vec<Metadata>,
+    schema_report: SchemaReport,
+}
+
+impl Db {
+    /// Serializes the database into a snapshot for [`Db::from_snapshot`].
+    pub fn to_snapshot(&self) -> Vec<u8> {
+        let snapshot = SnapshotRef {
+            header: Header::new(self.locale),
+            globals: &self.globals,
+            cards: &self.collection.0,
+            sets: self.collection.0.iter().map(|card| card.set).collect(),
+            metadata: &self.metadata,
+            schema_report: &self.schema_report,
+        };
+        bincode::serialize(&snapshot).expect("Database is always serializable")
+    }
+
+    /// Loads a database from a snapshot written by [`Db::to_snapshot`] with the same version of
+    /// this crate.
+    pub fn from_snapshot(bytes: &[u8]) -> Result<Self, DbError> {
+        // Check the header alone first, the rest may not even decode if the layout changed.
+        let header: Header = bincode::deserialize(bytes)?;
+        let locale = header
+            .locale
+            .parse()
+            .map_err(|_| DbError::SnapshotVersion)?;
+        if header != Header::new(locale) {
+            return Err(DbError::SnapshotVersion);
+        }
+
+        let snapshot: Snapshot = text::interning(|| bincode::deserialize(bytes))?;
+        if snapshot.cards.len() != snapshot.sets.len() {
+            return Err(DbError::SnapshotVersion);
+        }
+        let cards = snapshot
+            .cards
+            .into_iter()
+            .zip(snapshot.sets)
+            .map(|(mut card, set)| {
+                card.set = set;
+                card
+            })
+            .collect();
+
+        Ok(Db {
+            locale,
+            globals: snapshot.globals,
+            collection: Collection(cards),
+            metadata: snapshot.metadata,
+            schema_report: snapshot.schema_report,
+        })
+    }
+}
+
+/// 64-bit FNV-1a, stable across platforms and Rust versions unlike `DefaultHasher`.
+#[derive(Debug)]
+pub(crate) struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Fnv1a(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Snapshot file in `cache_dir` for data hashing to `hash`.
+pub(crate) fn cache_path(cache_dir: &Path, locale: Locale, hash: u64) -> PathBuf {
+    cache_dir.join(format!("db-{}-{:016x}.bin", locale, hash))
+}
+
+/// Reads a cached snapshot, treating any failure as a cache miss.
+pub(crate) fn read_cache(path: &Path) -> Option<Db> {
+    Db::from_snapshot(&fs::read(path).ok()?).ok()
+}
+
+/// Writes a snapshot to the cache. Failures are ignored, the data is simply parsed again next
+/// time.
+pub(crate) fn write_cache(path: &Path, db: &Db) {
+    let temp = path.with_extension("tmp");
+    let written = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::write(&temp, db.to_snapshot()))
+        .and_then(|_| fs::rename(&temp, path));
+    if written.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+}
@@ -0,0 +1,382 @@
+//! String types of the `db` cards, which avoid a separate allocation per field and card.
+//!
+//! [`Symbol`]s are interned for the duration of a load, so the handful of distinct regions,
+//! keywords, rarities and so on are allocated once and shared by every card, along with anything
+//! parsed from them with [`Symbol::parsed`]. [`Text`] borrows from the source bytes while parsing
+//! embedded data that lives for `'static`.
+
+use crate::snapshot::Fnv1a;
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::{Serialize, Serializer};
+use std::any::Any;
+use std::borrow::{Borrow, Cow};
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{BuildHasherDefault, Hash, Hasher};
+use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+
+/// The string of a [`Symbol`] and the values parsed from it, at most one per type.
+#[derive(Debug)]
+struct SymbolData {
+    text: Box<str>,
+    parsed: Mutex<Vec<Box<dyn Any + Send + Sync>>>,
+}
+
+impl SymbolData {
+    fn new(text: &str) -> Arc<Self> {
+        Arc::new(SymbolData {
+            text: text.into(),
+            parsed: Mutex::default(),
+        })
+    }
+}
+
+/// Interner entry, hashed and compared as its string so it can be looked up by `&str`.
+struct Interned(Arc<SymbolData>);
+
+impl Borrow<str> for Interned {
+    fn borrow(&self) -> &str {
+        &self.0.text
+    }
+}
+
+impl Hash for Interned {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.text.hash(state)
+    }
+}
+
+impl PartialEq for Interned {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.text == other.0.text
+    }
+}
+
+impl Eq for Interned {}
+
+// Symbols are short, FNV beats the default SipHash by a wide margin on them.
+type Interner = HashSet<Interned, BuildHasherDefault<Fnv1a>>;
+
+thread_local! {
+    static INTERNER: RefCell<Option<Interner>> = const { RefCell::new(None) };
+    static SOURCE: Cell<Option<&'static str>> = const { Cell::new(None) };
+}
+
+/// Runs `f` with [`Symbol`]s deserialized on this thread interned in one shared set.
+pub(crate) fn interning<T>(f: impl FnOnce() -> T) -> T {
+    let outer = INTERNER.with(|interner| interner.replace(Some(Interner::default())));
+    let result = f();
+    INTERNER.with(|interner| interner.replace(outer));
+    result
+}
+
+/// Runs `f` with [`Text`]s deserialized on this thread borrowing from `source` when possible.
+///
+/// `source` must be the exact input handed to the deserializer.
+pub(crate) fn borrowing<T>(source: &'static [u8], f: impl FnOnce() -> T) -> T {
+    let outer = SOURCE.with(|current| current.replace(std::str::from_utf8(source).ok()));
+    let result = f();
+    SOURCE.with(|current| current.set(outer));
+    result
+}
+
+/// Returns `value` as a slice of the registered source if it points into it.
+fn borrow_from_source(value: &str) -> Option<&'static str> {
+    let source = SOURCE.with(Cell::get)?;
+    let start = (value.as_ptr() as usize).checked_sub(source.as_ptr() as usize)?;
+    source
+        .get(start..start + value.len())
+        .filter(|borrowed| *borrowed == value)
+}
+
+/// A short string repeated across many cards, such as a region, keyword or rarity.
+///
+/// The empty symbol doesn't allocate, which keeps defaulting missing fields cheap.
+#[derive(Clone, Default)]
+pub struct Symbol(Option<Arc<SymbolData>>);
+
+impl Symbol {
+    pub fn as_str(&self) -> &str {
+        self.0.as_deref().map_or("", |data| &data.text)
+    }
+
+    /// Parses the symbol with `parse` the first time a `T` is asked for, then returns a clone of
+    /// that value.
+    ///
+    /// The value is kept with the interned string, so it is parsed once per load rather than once
+    /// per card using the symbol. Errors aren't kept, and the empty symbol is parsed every time.
+    pub fn parsed<T, E>(&self, parse: impl FnOnce(&str) -> Result<T, E>) -> Result<T, E>
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        let data = match &self.0 {
+            Some(data) => data,
+            None => return parse(""),
+        };
+
+        let mut parsed = data.parsed.lock().unwrap();
+        if let Some(value) = parsed.iter().find_map(|value| value.downcast_ref::<T>()) {
+            return Ok(value.clone());
+        }
+        let value = parse(&data.text)?;
+        parsed.push(Box::new(value.clone()));
+        Ok(value)
+    }
+}
+
+impl PartialEq for Symbol {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for Symbol {}
+
+impl PartialOrd for Symbol {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Symbol {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl Hash for Symbol {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(value: &str) -> Self {
+        if value.is_empty() {
+            return Symbol(None);
+        }
+
+        INTERNER.with(|interner| match interner.borrow_mut().as_mut() {
+            Some(interner) => match interner.get(value) {
+                Some(interned) => Symbol(Some(interned.0.clone())),
+                None => {
+                    let data = SymbolData::new(value);
+                    interner.insert(Interned(data.clone()));
+                    Symbol(Some(data))
+                }
+            },
+            None => Symbol(Some(SymbolData::new(value))),
+        })
+    }
+}
+
+/// Card text, borrowed from embedded data where possible.
+#[derive(Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Text(Cow<'static, str>);
+
+impl Text {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether the text borrows from the data it was parsed from rather than owning a copy.
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self.0, Cow::Borrowed(_))
+    }
+}
+
+impl From<&str> for Text {
+    fn from(value: &str) -> Self {
+        Text(Cow::Owned(value.to_string()))
+    }
+}
+
+impl From<String> for Text {
+    fn from(value: String) -> Self {
+        Text(Cow::Owned(value))
+    }
+}
+
+macro_rules! impl_str_traits {
+    ($ty:ident) => {
+        impl Deref for $ty {
+            type Target = str;
+
+            fn deref(&self) -> &str {
+                self.as_str()
+            }
+        }
+
+        impl AsRef<str> for $ty {
+            fn as_ref(&self) -> &str {
+                self.as_str()
+            }
+        }
+
+        impl Borrow<str> for $ty {
+            fn borrow(&self) -> &str {
+                self.as_str()
+            }
+        }
+
+        impl fmt::Debug for $ty {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Debug::fmt(self.as_str(), f)
+            }
+        }
+
+        impl fmt::Display for $ty {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl PartialEq<str> for $ty {
+            fn eq(&self, other: &str) -> bool {
+                self.as_str() == other
+            }
+        }
+
+        impl PartialEq<&str> for $ty {
+            fn eq(&self, other: &&str) -> bool {
+                self.as_str() == *other
+            }
+        }
+
+        impl PartialEq<$ty> for str {
+            fn eq(&self, other: &$ty) -> bool {
+                self == other.as_str()
+            }
+        }
+
+        impl PartialEq<$ty> for &str {
+            fn eq(&self, other: &$ty) -> bool {
+                *self == other.as_str()
+            }
+        }
+
+        impl PartialEq<String> for $ty {
+            fn eq(&self, other: &String) -> bool {
+                self.as_str() == other
+            }
+        }
+
+        impl PartialEq<$ty> for String {
+            fn eq(&self, other: &$ty) -> bool {
+                self == other.as_str()
+            }
+        }
+
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+    };
+}
+
+impl_str_traits!(Symbol);
+impl_str_traits!(Text);
+
+struct SymbolVisitor;
+
+impl<'de> Visitor<'de> for SymbolVisitor {
+    type Value = Symbol;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a string")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Symbol, E> {
+        Ok(Symbol::from(value))
+    }
+}
+
+impl<'de> Deserialize<'de> for Symbol {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(SymbolVisitor)
+    }
+}
+
+struct TextVisitor;
+
+impl<'de> Visitor<'de> for TextVisitor {
+    type Value = Text;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a string")
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, value: &'de str) -> Result<Text, E> {
+        Ok(match borrow_from_source(value) {
+            Some(value) => Text(Cow::Borrowed(value)),
+            None => Text::from(value),
+        })
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Text, E> {
+        Ok(Text::from(value))
+    }
+
+    fn visit_string<E: de::Error>(self, value: String) -> Result<Text, E> {
+        Ok(Text::from(value))
+    }
+}
+
+impl<'de> Deserialize<'de> for Text {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(TextVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_borrows_from_source() {
+        static SOURCE: &[u8] = br#"["plain", "esc\"aped"]"#;
+
+        let texts: Vec<Text> = borrowing(SOURCE, || serde_json::from_slice(SOURCE)).unwrap();
+        assert_eq!(texts, vec!["plain", "esc\"aped"]);
+        assert!(texts[0].is_borrowed());
+        assert!(!texts[1].is_borrowed());
+
+        let texts: Vec<Text> = serde_json::from_slice(SOURCE).unwrap();
+        assert!(!texts[0].is_borrowed());
+    }
+
+    #[test]
+    fn symbols_are_interned() {
+        let symbols: Vec<Symbol> =
+            interning(|| serde_json::from_str(r#"["Ionia", "Ionia"]"#)).unwrap();
+        assert_eq!(symbols[0].as_ptr(), symbols[1].as_ptr());
+
+        let symbols: Vec<Symbol> = serde_json::from_str(r#"["Ionia", "Ionia"]"#).unwrap();
+        assert_ne!(symbols[0].as_ptr(), symbols[1].as_ptr());
+
+        assert_eq!(Symbol::from(""), Symbol::default());
+    }
+
+    #[test]
+    fn symbols_cache_parsed_values() {
+        let symbols: Vec<Symbol> =
+            interning(|| serde_json::from_str(r#"["Champion", "Champion"]"#)).unwrap();
+        let mut parses = 0;
+        let mut parse = |value: &str| -> Result<String, ()> {
+            parses += 1;
+            Ok(value.to_lowercase())
+        };
+        assert_eq!(symbols[0].parsed(&mut parse), Ok("champion".to_string()));
+        assert_eq!(symbols[1].parsed(&mut parse), Ok("champion".to_string()));
+        assert_eq!(parses, 1);
+
+        // Each type is cached on its own.
+        assert_eq!(symbols[1].parsed(|value| Ok::<_, ()>(value.len())), Ok(8));
+        assert_eq!(symbols[0].parsed(|_| Err::<usize, _>(())), Ok(8));
+        assert_eq!(Symbol::default().parsed(|_| Err::<u8, _>(())), Err(()));
+    }
+}
@@ -1,4 +1,8 @@
-use runeterra_database::db::Db;
+use runeterra_database::db::{
+    set_number, AssetResolver, Collection, Db, DbError, ExportFormat, ExportTable, LiveDb,
+    LoadOptions, Locale, Overrides, SchemaMode,
+};
+use std::time::Duration;
 
 #[test]
 // makes sure database json paths are set up correctly
@@ -6,3 +10,598 @@ fn basic() {
     let db = Db::new();
     assert_ne!(db.collection.0.len(), 0);
 }
+
+#[test]
+fn sets_are_discovered() {
+    let db = Db::new();
+    assert!(db.collection.0.iter().all(|card| card.set == 1));
+}
+
+#[test]
+fn set_file_names() {
+    assert_eq!(set_number("data/set1-en_us.json", "en_us"), Some(1));
+    assert_eq!(set_number("data/set12-en_us.json", "en_us"), Some(12));
+    assert_eq!(set_number("data/set2-de_de.json", "en_us"), None);
+    assert_eq!(set_number("data/globals-en_us.json", "en_us"), None);
+}
+
+#[test]
+fn duplicate_card_codes_are_rejected() {
+    let db = Db::new();
+    let json = serde_json::to_string(&db.collection.0[..2]).unwrap();
+
+    let mut collection = Collection::default();
+    collection
+        .extend_set(1, serde_json::from_str(&json).unwrap())
+        .unwrap();
+    let err = collection
+        .extend_set(2, serde_json::from_str(&json).unwrap())
+        .unwrap_err();
+
+    assert_eq!(err.card_code, db.collection.0[0].card_code);
+    assert_eq!(err.set, 2);
+    assert_eq!(collection.0.len(), 2);
+}
+
+#[test]
+fn locales() {
+    assert_eq!("en_us".parse::<Locale>().unwrap(), Locale::EnUs);
+    assert_eq!("de-DE".parse::<Locale>().unwrap(), Locale::DeDe);
+    assert!("xx_xx".parse::<Locale>().is_err());
+    assert_eq!(Locale::KoKr.to_string(), "ko_kr");
+
+    assert!(Locale::EnUs.is_embedded());
+    assert!(Locale::embedded().any(|locale| locale == Locale::EnUs));
+
+    let db = Db::with_locale(Locale::EnUs);
+    assert_eq!(db.locale, Locale::EnUs);
+    assert_ne!(db.collection.0.len(), 0);
+}
+
+#[test]
+fn from_dir_falls_back_to_embedded_data() {
+    let dir = std::env::temp_dir().join(format!("runeterra-database-{}", std::process::id()));
+    let data = dir.join("set2-en_us").join("en_us").join("data");
+    std::fs::create_dir_all(&data).unwrap();
+
+    let db = Db::new();
+    let mut card = serde_json::to_value(&db.collection.0[0]).unwrap();
+    card["cardCode"] = "02IO001".into();
+    std::fs::write(
+        data.join("set2-en_us.json"),
+        serde_json::to_vec(&vec![card]).unwrap(),
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("set2-en_us").join("metadata.json"),
+        r#"{"locales":["en_us"],"clientHash":"abc"}"#,
+    )
+    .unwrap();
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(&dir, data.join("loop")).unwrap();
+
+    let result = Db::from_dir(&dir);
+    std::fs::remove_dir_all(&dir).unwrap();
+    let from_dir = result.unwrap();
+
+    assert_eq!(from_dir.collection.0.len(), db.collection.0.len() + 1);
+    let card = from_dir.collection.0.last().unwrap();
+    assert_eq!((card.card_code.as_str(), card.set), ("02IO001", 2));
+    assert_eq!(from_dir.metadata.len(), 1);
+    assert_eq!(from_dir.metadata[0].client_hash.as_deref(), Some("abc"));
+}
+
+#[cfg(feature = "zip")]
+#[test]
+fn from_zips_falls_back_to_embedded_data() {
+    use runeterra_database::db::ZipImages;
+    use std::io::Write;
+    use zip::write::{FileOptions, ZipWriter};
+
+    let path = std::env::temp_dir().join(format!("set2-lite-en_us-{}.zip", std::process::id()));
+
+    let db = Db::new();
+    let mut card = serde_json::to_value(&db.collection.0[0]).unwrap();
+    card["cardCode"] = "02IO001".into();
+
+    let mut zip = ZipWriter::new(std::fs::File::create(&path).unwrap());
+    zip.start_file("metadata.json", FileOptions::default())
+        .unwrap();
+    zip.write_all(br#"{"locales":["en_us"]}"#).unwrap();
+    zip.start_file("en_us/data/set2-en_us.json", FileOptions::default())
+        .unwrap();
+    zip.write_all(&serde_json::to_vec(&vec![card]).unwrap())
+        .unwrap();
+    zip.start_file("en_us/img/cards/02IO001.png", FileOptions::default())
+        .unwrap();
+    zip.write_all(b"png").unwrap();
+    zip.start_file("de_de/img/cards/02IO001.png", FileOptions::default())
+        .unwrap();
+    zip.write_all(b"de png").unwrap();
+    zip.finish().unwrap();
+
+    let from_zips = Db::from_zips([&path]);
+    let images = ZipImages::index([&path]);
+    let image = images
+        .as_ref()
+        .ok()
+        .and_then(|images| images.read(Locale::EnUs, "02IO001.png").ok());
+    std::fs::remove_file(&path).unwrap();
+
+    let from_zips = from_zips.unwrap();
+    assert_eq!(from_zips.collection.0.len(), db.collection.0.len() + 1);
+    assert_eq!(from_zips.collection.0.last().unwrap().set, 2);
+    assert_eq!(from_zips.metadata.len(), 1);
+
+    let images = images.unwrap();
+    assert_eq!(images.len(), 2);
+    assert!(images.contains(Locale::EnUs, "02IO001.png"));
+    assert!(images.contains(Locale::DeDe, "02IO001.png"));
+    assert!(!images.contains(Locale::FrFr, "02IO001.png"));
+    assert_eq!(image.unwrap(), b"png");
+}
+
+fn write_set2(dir: &std::path::Path, cards: Vec<serde_json::Value>) {
+    std::fs::create_dir_all(dir).unwrap();
+    std::fs::write(
+        dir.join("set2-en_us.json"),
+        serde_json::to_vec(&cards).unwrap(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn load_errors() {
+    assert!(Db::load().is_ok());
+    assert!(matches!(
+        Db::load_with_locale(Locale::DeDe),
+        Err(DbError::LocaleNotEmbedded(Locale::DeDe))
+    ));
+    assert!(matches!(
+        Db::from_dir("does/not/exist"),
+        Err(DbError::MissingFile(_))
+    ));
+
+    let db = Db::new();
+    let dir =
+        std::env::temp_dir().join(format!("runeterra-database-errors-{}", std::process::id()));
+
+    let mut valid = serde_json::to_value(&db.collection.0[0]).unwrap();
+    valid["cardCode"] = "02IO001".into();
+    let mut invalid = valid.clone();
+    invalid["cardCode"] = "02IO002".into();
+    invalid["attack"] = "many".into();
+    write_set2(&dir, vec![valid, invalid]);
+    let json = Db::from_dir(&dir);
+
+    let duplicate = serde_json::to_value(&db.collection.0[0]).unwrap();
+    write_set2(&dir, vec![duplicate]);
+    let duplicate = Db::from_dir(&dir);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    match json {
+        Err(DbError::Json {
+            file,
+            line,
+            card_code,
+            ..
+        }) => {
+            assert!(file.ends_with("set2-en_us.json"));
+            assert_eq!(line, 1);
+            assert_eq!(card_code.as_deref(), Some("02IO002"));
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+
+    match duplicate {
+        Err(DbError::DuplicateCardCode(err)) => {
+            assert_eq!(err.card_code, db.collection.0[0].card_code);
+            assert_eq!(err.set, 2);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn schema_modes() {
+    let strict = LoadOptions {
+        schema: SchemaMode::Strict,
+        ..LoadOptions::default()
+    };
+    assert!(Db::load_with_options(strict.clone()).is_ok());
+
+    let db = Db::new();
+    let dir =
+        std::env::temp_dir().join(format!("runeterra-database-schema-{}", std::process::id()));
+    let mut card = serde_json::to_value(&db.collection.0[0]).unwrap();
+    card["cardCode"] = "02IO001".into();
+    card["regionRefs"] = serde_json::json!(["Ionia"]);
+    card["newField"] = true.into();
+    card.as_object_mut().unwrap().remove("flavorText");
+    write_set2(&dir, vec![card]);
+
+    let tolerant = Db::from_dir(&dir);
+    let report = Db::from_dir_with_options(
+        &dir,
+        LoadOptions {
+            schema: SchemaMode::Report,
+            ..LoadOptions::default()
+        },
+    );
+    let strict = Db::from_dir_with_options(&dir, strict);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let tolerant = tolerant.unwrap();
+    let card = tolerant.collection.0.last().unwrap();
+    assert_eq!(card.flavor_text, "");
+    assert_eq!(card.region_refs, vec!["Ionia"]);
+    assert!(tolerant.schema_report.is_empty());
+
+    let report = report.unwrap();
+    assert_eq!(report.collection.0.len(), tolerant.collection.0.len());
+    assert_eq!(report.schema_report.0.len(), 1);
+    assert_eq!(report.schema_report.0[0].unknown, vec!["newField"]);
+
+    match strict {
+        Err(DbError::Schema(report)) => {
+            assert_eq!(report.0.len(), 1);
+            assert_eq!(report.0[0].card_code.as_deref(), Some("02IO001"));
+            assert_eq!(report.0[0].unknown, vec!["newField"]);
+            assert_eq!(report.0[0].missing, vec!["flavorText"]);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn snapshots() {
+    let db = Db::new();
+    let snapshot = db.to_snapshot();
+    let from_snapshot = Db::from_snapshot(&snapshot).unwrap();
+    assert_eq!(from_snapshot.locale, db.locale);
+    assert_eq!(from_snapshot.collection.0, db.collection.0);
+    assert_eq!(from_snapshot.globals.keywords(), db.globals.keywords());
+
+    assert!(Db::from_snapshot(&snapshot[..snapshot.len() / 2]).is_err());
+    assert!(Db::from_snapshot(b"not a snapshot").is_err());
+
+    let cache_dir =
+        std::env::temp_dir().join(format!("runeterra-database-cache-{}", std::process::id()));
+    let options = LoadOptions {
+        cache_dir: Some(cache_dir.clone()),
+        ..LoadOptions::default()
+    };
+    let first = Db::load_with_options(options.clone());
+    let cached = std::fs::read_dir(&cache_dir).map(|entries| entries.count());
+    let second = Db::load_with_options(options);
+    std::fs::remove_dir_all(&cache_dir).unwrap();
+
+    assert_eq!(cached.unwrap(), 1);
+    assert_eq!(first.unwrap().collection.0, db.collection.0);
+    assert_eq!(second.unwrap().collection.0, db.collection.0);
+}
+
+#[test]
+fn repeated_strings_are_shared() {
+    let db = Db::new();
+    let ionia: Vec<_> = db
+        .collection
+        .0
+        .iter()
+        .filter(|card| card.region_ref == "Ionia")
+        .collect();
+
+    assert!(ionia.len() > 1);
+    assert!(ionia
+        .iter()
+        .all(|card| card.region_ref.as_ptr() == ionia[0].region_ref.as_ptr()));
+    assert_eq!(ionia[0].region.as_ptr(), ionia[0].region_ref.as_ptr());
+}
+
+#[test]
+fn globals_lookups() {
+    let db = Db::new();
+    let globals = &db.globals;
+
+    assert_eq!(globals.region("PiltoverZaun").unwrap().abbreviation, "PZ");
+    assert_eq!(
+        globals.region_by_abbreviation("SI").unwrap().name,
+        "Shadow Isles"
+    );
+    assert_eq!(globals.keyword("QuickStrike").unwrap().name, "Quick Attack");
+    assert_eq!(globals.spell_speed("Burst").unwrap().name, "Burst");
+    assert_eq!(globals.rarity("Epic").unwrap().name, "Epic");
+    assert!(globals.keyword("Missing").is_none());
+
+    // Changes after a lookup are picked up by the next one.
+    let mut globals = Db::new().globals;
+    assert_eq!(globals.region("Demacia").unwrap().abbreviation, "DE");
+    let demacia = globals
+        .regions()
+        .iter()
+        .position(|region| region.name_ref == "Demacia")
+        .unwrap();
+    globals.regions_mut().remove(demacia);
+    assert!(globals.region("Demacia").is_none());
+    let last = globals.regions().last().unwrap().name_ref.clone();
+    assert_eq!(globals.region(&last).unwrap().name_ref, last);
+}
+
+#[test]
+fn overrides() {
+    let mut db = Db::new();
+    let overrides = Overrides::from_slice(
+        "overrides.json",
+        br#"{"01IO012T2": {"keywordRefs": ["Burst", "Fleeting"], "cost": 2}}"#,
+    )
+    .unwrap();
+
+    let overridden = db.apply_overrides(&overrides).unwrap();
+    assert_eq!(overridden.len(), 2);
+    assert_eq!(overridden[0].card_code, "01IO012T2");
+    assert_eq!(overridden[0].field, "cost");
+    assert_eq!(overridden[0].previous, 3);
+    assert_eq!(overridden[0].value, 2);
+
+    let card = db
+        .collection
+        .0
+        .iter()
+        .find(|card| card.card_code == "01IO012T2")
+        .unwrap();
+    assert_eq!(card.cost, 2);
+    assert_eq!(card.keyword_refs, vec!["Burst", "Fleeting"]);
+    assert_eq!(card.set, 1);
+
+    let retyped = Overrides::from_slice(
+        "overrides.json",
+        br#"{"01IO012T2": {"type": "Unit"}, "01IO012": {"supertype": "Champion", "supertypeRef": "Legend"}}"#,
+    )
+    .unwrap();
+    db.apply_overrides(&retyped).unwrap();
+    let card = |card_code: &str| {
+        db.collection
+            .0
+            .iter()
+            .find(|card| card.card_code == card_code)
+            .unwrap()
+    };
+    assert_eq!(card("01IO012T2").type_ref, "Unit");
+    assert_eq!(card("01IO012").supertype_ref, "Legend");
+
+    let invalid = Overrides::from_slice(
+        "overrides.json",
+        br#"{"99XX999": {"cost": 1}, "01IO012T2": {"costs": 1, "cardCode": "x"}}"#,
+    )
+    .unwrap();
+    match db.apply_overrides(&invalid) {
+        Err(DbError::InvalidOverrides {
+            unknown_cards,
+            unknown_fields,
+        }) => {
+            assert_eq!(unknown_cards, vec!["99XX999"]);
+            assert_eq!(unknown_fields.len(), 2);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+
+    let mistyped =
+        Overrides::from_slice("overrides.json", br#"{"01IO012T2": {"cost": "free"}}"#).unwrap();
+    match db.apply_overrides(&mistyped) {
+        Err(DbError::Json { card_code, .. }) => {
+            assert_eq!(card_code.as_deref(), Some("01IO012T2"))
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn export() {
+    let db = Db::new();
+
+    let mut csv = Vec::new();
+    db.export(ExportTable::Cards, ExportFormat::Csv, &mut csv)
+        .unwrap();
+    let mut reader = csv::Reader::from_reader(&csv[..]);
+    assert_eq!(
+        reader.headers().unwrap(),
+        vec![
+            "code",
+            "name",
+            "region",
+            "cost",
+            "attack",
+            "health",
+            "type",
+            "rarity",
+            "set",
+            "keywords",
+            "description"
+        ]
+    );
+    assert_eq!(reader.records().count(), db.collection.0.len());
+
+    let mut jsonl = Vec::new();
+    db.export(ExportTable::Cards, ExportFormat::JsonLines, &mut jsonl)
+        .unwrap();
+    let rows = jsonl
+        .split(|&byte| byte == b'\n')
+        .filter(|line| !line.is_empty())
+        .map(|line| serde_json::from_slice::<serde_json::Value>(line).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(rows.len(), db.collection.0.len());
+    let row = rows.iter().find(|row| row["code"] == "01IO012T2").unwrap();
+    assert_eq!(row["set"], 1);
+    assert!(row["keywords"].is_array());
+
+    let mut keywords = Vec::new();
+    db.export(ExportTable::Keywords, ExportFormat::Csv, &mut keywords)
+        .unwrap();
+    let keywords = String::from_utf8(keywords).unwrap();
+    assert!(keywords.starts_with("code,keyword,keyword_ref\n"));
+    assert_eq!(keywords.lines().count(), db.keyword_rows().count() + 1);
+
+    // Keyword names and refs of different lengths lose neither.
+    let mut uneven = Db::new();
+    let card = uneven
+        .collection
+        .0
+        .iter_mut()
+        .find(|card| card.keyword_refs.len() >= 2)
+        .unwrap();
+    let code = card.card_code.clone();
+    let count = card.keyword_refs.len();
+    card.keywords.truncate(1);
+    let rows: Vec<_> = uneven
+        .keyword_rows()
+        .filter(|row| row.code == code)
+        .collect();
+    assert_eq!(rows.len(), count);
+    let last = rows.last().unwrap();
+    let details = uneven.globals.keyword(last.keyword_ref).unwrap();
+    assert_eq!(last.keyword, details.name);
+
+    let mut associated = Vec::new();
+    db.export(
+        ExportTable::AssociatedCards,
+        ExportFormat::JsonLines,
+        &mut associated,
+    )
+    .unwrap();
+    let edges = db
+        .collection
+        .0
+        .iter()
+        .map(|card| card.associated_card_refs.len())
+        .sum::<usize>();
+    assert_eq!(
+        String::from_utf8(associated).unwrap().lines().count(),
+        edges
+    );
+}
+
+#[test]
+fn asset_resolver() {
+    let dir = std::env::temp_dir().join(format!("runeterra-assets-{}", std::process::id()));
+    let cards = dir
+        .join("set1-en_us")
+        .join("en_us")
+        .join("img")
+        .join("cards");
+    let regions = dir
+        .join("core-en_us")
+        .join("en_us")
+        .join("img")
+        .join("regions");
+    let other_locale = dir
+        .join("set1-fr_fr")
+        .join("fr_fr")
+        .join("img")
+        .join("cards");
+    for (dir, file_name) in &[
+        (&cards, "01IO012T2.png"),
+        (&cards, "01IO012T2-alt.png"),
+        (&cards, "01IO012T2-alt-full.png"),
+        (&regions, "icon-ionia.png"),
+        (&other_locale, "01IO012T2-full.png"),
+    ] {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(dir.join(file_name), b"").unwrap();
+    }
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(&dir, cards.join("loop")).unwrap();
+
+    let result = AssetResolver::index(&dir, Locale::EnUs);
+    std::fs::remove_dir_all(&dir).unwrap();
+    let resolver = result.unwrap();
+    assert_eq!(resolver.len(), 4);
+
+    let db = Db::new();
+    let card = db
+        .collection
+        .0
+        .iter()
+        .find(|card| card.card_code == "01IO012T2")
+        .unwrap();
+    let images = resolver.card(card);
+    assert_eq!(images.game, Some(cards.join("01IO012T2.png").as_path()));
+    assert_eq!(images.full, None);
+    assert_eq!(
+        images.alternates,
+        vec![
+            cards.join("01IO012T2-alt-full.png").as_path(),
+            cards.join("01IO012T2-alt.png").as_path()
+        ]
+    );
+
+    let ionia = db.globals.region("Ionia").unwrap();
+    assert_eq!(
+        resolver.region_icon(ionia),
+        Some(regions.join("icon-ionia.png").as_path())
+    );
+
+    let missing = resolver.missing(&db);
+    assert_eq!(
+        missing.len(),
+        db.collection.0.len() * 2 + db.globals.regions().len() - 2
+    );
+    assert!(missing
+        .iter()
+        .any(|image| image.owner == "01IO012T2" && image.url.ends_with("01IO012T2-full.png")));
+    assert!(missing.iter().all(|image| image.owner != "Ionia"));
+}
+
+#[test]
+fn live_reload() {
+    let db = Db::new();
+    let dir = std::env::temp_dir().join(format!("runeterra-database-live-{}", std::process::id()));
+    let mut card = serde_json::to_value(&db.collection.0[0]).unwrap();
+    card["cardCode"] = "02IO001".into();
+    write_set2(&dir, vec![card.clone()]);
+
+    let overrides =
+        Overrides::from_slice("overrides.json", br#"{"02IO001": {"cost": 9}}"#).unwrap();
+    let live = LiveDb::watch_with_overrides(
+        &dir,
+        LoadOptions::default(),
+        overrides,
+        Duration::from_secs(3600),
+    )
+    .unwrap();
+    let before = live.current();
+    let unchanged = live.reload();
+
+    let mut second = card.clone();
+    second["cardCode"] = "02IO002".into();
+    write_set2(&dir, vec![card, second]);
+    let reloaded = live.reload();
+    let after = live.current();
+
+    std::fs::write(dir.join("set2-en_us.json"), "[{").unwrap();
+    let failed = live.reload();
+
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert!(!unchanged.unwrap());
+    assert!(reloaded.unwrap());
+    assert!(failed.is_err());
+    assert!(live.last_error().is_some());
+
+    assert_eq!(before.version, 0);
+    assert_eq!(before.db.collection.0.len(), db.collection.0.len() + 1);
+    assert_eq!(after.version, 1);
+    assert_eq!(after.db.collection.0.len(), db.collection.0.len() + 2);
+    assert_eq!(live.version(), 1);
+
+    for versioned in &[before, after] {
+        let card = versioned
+            .db
+            .collection
+            .0
+            .iter()
+            .find(|card| card.card_code == "02IO001")
+            .unwrap();
+        assert_eq!(card.cost, 9);
+    }
+}
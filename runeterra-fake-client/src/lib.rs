@@ -0,0 +1,125 @@
+//! Serves the Legends of Runeterra [`Game Client API`] from a scenario or recorded session, so
+//! trackers can be run end-to-end where the game can't.
+//!
+//! [`Game Client API`]: https://developer.riotgames.com/docs/lor#game-client-api
+
+use runeterra_game_api::{Failure, Replayer};
+use serde::Serialize;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::thread;
+
+/// A fake game answering Game Client API requests with the responses of a replayed scenario or
+/// recorded session.
+///
+/// Recorded failures are served as close to the original as a listening server can: statuses and
+/// invalid bodies as such, refused connections by hanging up without an answer and timeouts by
+/// never answering.
+#[derive(Debug)]
+pub struct FakeClient {
+    listener: TcpListener,
+    replayer: Replayer,
+}
+
+impl FakeClient {
+    /// Binds to `addr`, use port `21337` to stand in for the game.
+    pub fn bind(addr: impl ToSocketAddrs, replayer: Replayer) -> io::Result<Self> {
+        Ok(FakeClient {
+            listener: TcpListener::bind(addr)?,
+            replayer,
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Answers requests forever, each connection on its own thread.
+    pub fn serve(self) -> io::Result<()> {
+        for stream in self.listener.incoming() {
+            let stream = stream?;
+            let replayer = self.replayer.clone();
+            thread::spawn(move || {
+                let _ = handle(stream, &replayer);
+            });
+        }
+        Ok(())
+    }
+
+    /// Answers requests on a background thread.
+    pub fn spawn(self) -> thread::JoinHandle<io::Result<()>> {
+        thread::spawn(move || self.serve())
+    }
+}
+
+enum Response {
+    Json(String),
+    Status(u16),
+    HangUp,
+    NoAnswer,
+}
+
+impl Response {
+    fn new(response: &impl Serialize, failure: &Option<Failure>) -> Self {
+        match failure {
+            None => serde_json::to_string(response).map_or(Response::Status(500), Response::Json),
+            Some(Failure::Status(status)) => Response::Status(*status),
+            Some(Failure::Decode { body, .. }) => Response::Json(body.clone()),
+            Some(Failure::ConnectionRefused) => Response::HangUp,
+            Some(Failure::Timeout) => Response::NoAnswer,
+        }
+    }
+}
+
+fn handle(stream: TcpStream, replayer: &Replayer) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Requests to the API have no body, skip the headers.
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (parts.next(), parts.next().unwrap_or_default());
+    let path = path.split('?').next().unwrap_or_default();
+    let snapshot = replayer.snapshot();
+    let failures = &snapshot.failures;
+    let response = match (method, path) {
+        (Some("GET"), "/static-decklist") => {
+            Response::new(&snapshot.static_decklist, &failures.static_decklist)
+        }
+        (Some("GET"), "/positional-rectangles") => Response::new(
+            &snapshot.positional_rectangles,
+            &failures.positional_rectangles,
+        ),
+        (Some("GET"), "/expeditions-state") => {
+            Response::new(&snapshot.expeditions_state, &failures.expeditions_state)
+        }
+        (Some("GET"), "/game-result") => {
+            Response::new(&snapshot.game_result, &failures.game_result)
+        }
+        _ => Response::Status(404),
+    };
+
+    let mut stream = stream;
+    match response {
+        Response::Json(body) => write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        ),
+        Response::Status(status) => write!(
+            stream,
+            "HTTP/1.1 {} {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            status,
+            if status == 404 { "Not Found" } else { "Error" }
+        ),
+        Response::HangUp => return Ok(()),
+        // Wait for the client to give up and close the connection.
+        Response::NoAnswer => return io::copy(&mut reader, &mut io::sink()).map(drop),
+    }?;
+    stream.flush()
+}
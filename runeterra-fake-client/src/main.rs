@@ -0,0 +1,66 @@
+use runeterra_fake_client::FakeClient;
+use runeterra_game_api::{Replayer, Scenario, DEFAULT_PORT};
+use std::env;
+use std::fs::File;
+use std::io::BufReader;
+use std::process;
+
+const USAGE: &str = "\
+Usage: runeterra-fake-client [--port <port>] [--speed <factor>] [--loop] <scenario>
+
+Serves the Game Client API from <scenario>, a file with one JSON event per line such as a
+session recorded with runeterra_game_api::Recorder.";
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    process::exit(2)
+}
+
+fn main() {
+    let mut port = DEFAULT_PORT;
+    let mut speed = 1.0;
+    let mut looping = false;
+    let mut path = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--port" => {
+                port = args
+                    .next()
+                    .and_then(|port| port.parse().ok())
+                    .unwrap_or_else(|| usage())
+            }
+            "--speed" => {
+                speed = args
+                    .next()
+                    .and_then(|speed| speed.parse().ok())
+                    .filter(|speed: &f64| speed.is_finite() && *speed > 0.0)
+                    .unwrap_or_else(|| usage())
+            }
+            "--loop" => looping = true,
+            _ if arg.starts_with('-') || path.is_some() => usage(),
+            _ => path = Some(arg),
+        }
+    }
+    let path = path.unwrap_or_else(|| usage());
+
+    let scenario = File::open(&path).and_then(|file| Scenario::read(BufReader::new(file)));
+    let scenario = scenario.unwrap_or_else(|err| {
+        eprintln!("{}: {}", path, err);
+        process::exit(1)
+    });
+
+    let events = scenario.events().len();
+    let replayer = Replayer::with_speed(scenario, speed).looping(looping);
+    let client = FakeClient::bind(("127.0.0.1", port), replayer).unwrap_or_else(|err| {
+        eprintln!("Failed to listen on port {}: {}", port, err);
+        process::exit(1)
+    });
+    eprintln!("Serving {} events from {} on port {}", events, path, port);
+
+    if let Err(err) = client.serve() {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
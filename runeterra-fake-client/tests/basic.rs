@@ -0,0 +1,75 @@
+use runeterra_fake_client::FakeClient;
+use runeterra_game_api::{Client, ClientBuilder, Error, GameState, Replayer, Scenario};
+use std::fs::File;
+use std::io::BufReader;
+use std::time::Duration;
+
+fn scenario() -> Scenario {
+    let file = File::open(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/scenarios/basic.jsonl"
+    ))
+    .unwrap();
+    Scenario::read(BufReader::new(file)).unwrap()
+}
+
+/// Serves `scenario` paused at `elapsed`, so what is served doesn't depend on how fast the test
+/// runs.
+fn serve_at(scenario: &Scenario, elapsed: Duration) -> Client {
+    let mut replayer = Replayer::with_speed(scenario.clone(), 0.0);
+    replayer.seek(elapsed);
+    let fake = FakeClient::bind("127.0.0.1:0", replayer).unwrap();
+    let client = ClientBuilder::new()
+        .port(fake.local_addr().unwrap().port())
+        .host("127.0.0.1")
+        .build()
+        .unwrap();
+    fake.spawn();
+    client
+}
+
+#[tokio::test]
+async fn serves_scenario() {
+    let scenario = scenario();
+    assert_eq!(scenario.events().len(), 4);
+    assert_eq!(scenario.duration(), Duration::from_secs(60));
+
+    // Events at 0, 5, 20 and 60 seconds.
+    let client = serve_at(&scenario, Duration::from_secs(1));
+    let rectangles = client.get_positional_rectangles().await.unwrap();
+    assert_eq!(rectangles.game_state, GameState::Menus);
+    assert_eq!(client.get_game_result().await.unwrap().game_id, -1);
+
+    let client = serve_at(&scenario, Duration::from_secs(30));
+    let rectangles = client.get_positional_rectangles().await.unwrap();
+    assert_eq!(rectangles.game_state, GameState::InProgress);
+    let deck = client.get_static_decklist().await.unwrap();
+    assert_eq!(deck.cards_in_deck.unwrap()["01NX038"], 2);
+
+    let client = serve_at(&scenario, Duration::from_secs(90));
+    let result = client.get_game_result().await.unwrap();
+    assert_eq!((result.game_id, result.local_player_won), (1, true));
+    assert_eq!(client.get_static_decklist().await.unwrap().deck_code, None);
+}
+
+#[tokio::test]
+async fn serves_failures() {
+    let scenario = Scenario::read(
+        &br#"{"at":0.0,"failures":{"game-result":{"status":503}}}
+{"at":1.0,"failures":{"static-decklist":{"decode":{"body":"{\"DeckCode\"","message":"EOF"}}}}
+"#[..],
+    )
+    .unwrap();
+
+    let client = serve_at(&scenario, Duration::from_secs(2));
+    match client.get_game_result().await {
+        Err(Error::Status { status, .. }) => assert_eq!(status, 503),
+        other => panic!("unexpected {:?}", other),
+    }
+    match client.get_static_decklist().await {
+        Err(Error::Decode { body, .. }) => assert_eq!(body, "{\"DeckCode\""),
+        other => panic!("unexpected {:?}", other),
+    }
+    let rectangles = client.get_positional_rectangles().await.unwrap();
+    assert_eq!(rectangles.game_state, GameState::Menus);
+}
@@ -0,0 +1,16 @@
+use futures::StreamExt;
+use runeterra_game_api::{watch, Client, Recorder, WatchConfig, DEFAULT_PORT};
+use std::fs::File;
+
+/// Records a session of the running game to `session.jsonl` until interrupted.
+#[tokio::main]
+async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let recorder = Recorder::new(Client::new(DEFAULT_PORT), File::create("session.jsonl")?);
+    let mut changes = Box::pin(watch(recorder.clone(), WatchConfig::default()));
+
+    while let Some(change) = changes.next().await {
+        dbg!(&change);
+        recorder.flush()?;
+    }
+    Ok(())
+}
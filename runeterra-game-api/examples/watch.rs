@@ -0,0 +1,12 @@
+use futures::StreamExt;
+use runeterra_game_api::{Client, WatchConfig, DEFAULT_PORT};
+
+#[tokio::main]
+async fn main() {
+    let client = Client::new(DEFAULT_PORT);
+    let mut changes = Box::pin(client.watch(WatchConfig::default()));
+
+    while let Some(change) = changes.next().await {
+        dbg!(&change);
+    }
+}
@@ -0,0 +1,205 @@
+use crate::{Client, ExpeditionsState, GameResult, PositionalRectangles, StaticDecklist};
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// The endpoints of the Game Client API.
+///
+/// Implemented by [`Client`] and [`ScriptedApi`], code written against it can be tested without
+/// the game or a mock server.
+#[async_trait]
+pub trait GameApi {
+    async fn get_static_decklist(&self) -> Result<StaticDecklist, crate::Error>;
+
+    async fn get_positional_rectangles(&self) -> Result<PositionalRectangles, crate::Error>;
+
+    async fn get_expeditions_state(&self) -> Result<ExpeditionsState, crate::Error>;
+
+    async fn get_game_result(&self) -> Result<GameResult, crate::Error>;
+}
+
+#[async_trait]
+impl GameApi for Client {
+    async fn get_static_decklist(&self) -> Result<StaticDecklist, crate::Error> {
+        Client::get_static_decklist(self).await
+    }
+
+    async fn get_positional_rectangles(&self) -> Result<PositionalRectangles, crate::Error> {
+        Client::get_positional_rectangles(self).await
+    }
+
+    async fn get_expeditions_state(&self) -> Result<ExpeditionsState, crate::Error> {
+        Client::get_expeditions_state(self).await
+    }
+
+    async fn get_game_result(&self) -> Result<GameResult, crate::Error> {
+        Client::get_game_result(self).await
+    }
+}
+
+/// Responses of one endpoint, answered in order.
+#[derive(Debug)]
+struct Queue<T> {
+    responses: VecDeque<Result<T, crate::Error>>,
+    last: Option<T>,
+}
+
+impl<T> Default for Queue<T> {
+    fn default() -> Self {
+        Queue {
+            responses: VecDeque::new(),
+            last: None,
+        }
+    }
+}
+
+impl<T: Clone> Queue<T> {
+    fn next(&mut self, endpoint: &str) -> Result<T, crate::Error> {
+        match self.responses.pop_front() {
+            Some(Ok(value)) => {
+                self.last = Some(value.clone());
+                Ok(value)
+            }
+            Some(Err(err)) => {
+                self.last = None;
+                Err(err)
+            }
+            None => self
+                .last
+                .clone()
+                .ok_or_else(|| crate::Error::ConnectionRefused {
+                    url: endpoint.to_string(),
+                    source: None,
+                }),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Script {
+    static_decklist: Queue<StaticDecklist>,
+    positional_rectangles: Queue<PositionalRectangles>,
+    expeditions_state: Queue<ExpeditionsState>,
+    game_result: Queue<GameResult>,
+}
+
+/// A [`GameApi`] answering every endpoint from a queue of scripted responses.
+///
+/// Once an endpoint's queue is empty its last successful response is repeated, like the game
+/// staying in the same state. With nothing to repeat, because the endpoint never answered or its
+/// last response was a scripted error, it fails with [`crate::Error::ConnectionRefused`] like the
+/// game not running. Clones share the same queues, so responses can be pushed while another clone
+/// is being polled.
+#[derive(Debug, Clone, Default)]
+pub struct ScriptedApi {
+    script: Arc<Mutex<Script>>,
+}
+
+impl ScriptedApi {
+    pub fn new() -> Self {
+        ScriptedApi::default()
+    }
+
+    pub fn push_static_decklist(&self, response: Result<StaticDecklist, crate::Error>) {
+        self.script().static_decklist.responses.push_back(response);
+    }
+
+    pub fn push_positional_rectangles(&self, response: Result<PositionalRectangles, crate::Error>) {
+        self.script()
+            .positional_rectangles
+            .responses
+            .push_back(response);
+    }
+
+    pub fn push_expeditions_state(&self, response: Result<ExpeditionsState, crate::Error>) {
+        self.script()
+            .expeditions_state
+            .responses
+            .push_back(response);
+    }
+
+    pub fn push_game_result(&self, response: Result<GameResult, crate::Error>) {
+        self.script().game_result.responses.push_back(response);
+    }
+
+    fn script(&self) -> std::sync::MutexGuard<'_, Script> {
+        self.script.lock().unwrap()
+    }
+}
+
+#[async_trait]
+impl GameApi for ScriptedApi {
+    async fn get_static_decklist(&self) -> Result<StaticDecklist, crate::Error> {
+        self.script().static_decklist.next("/static-decklist")
+    }
+
+    async fn get_positional_rectangles(&self) -> Result<PositionalRectangles, crate::Error> {
+        self.script()
+            .positional_rectangles
+            .next("/positional-rectangles")
+    }
+
+    async fn get_expeditions_state(&self) -> Result<ExpeditionsState, crate::Error> {
+        self.script().expeditions_state.next("/expeditions-state")
+    }
+
+    async fn get_game_result(&self) -> Result<GameResult, crate::Error> {
+        self.script().game_result.next("/game-result")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{watch, Change, GameApi, ScriptedApi, StaticDecklist, WatchConfig};
+    use futures::StreamExt;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_scripted_watch() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let menus = StaticDecklist {
+            deck_code: None,
+            cards_in_deck: None,
+        };
+        let in_game = StaticDecklist {
+            deck_code: Some(
+                "CEAAECABAMGA6EYXEYVS4NYIAECQCGY5FAVTCMRVAICACAYCBELDGBABAURCMKJW".to_string(),
+            ),
+            cards_in_deck: Some([("01NX012".to_string(), 2)].iter().cloned().collect()),
+        };
+        let closed = crate::Error::Status {
+            endpoint: "/static-decklist".to_string(),
+            status: 503,
+        };
+
+        let api = ScriptedApi::new();
+        api.push_static_decklist(Ok(menus.clone()));
+        api.push_static_decklist(Ok(menus.clone()));
+        api.push_static_decklist(Err(closed));
+        api.push_static_decklist(Ok(in_game.clone()));
+
+        let config = WatchConfig {
+            static_decklist: Some(Duration::from_millis(1)),
+            positional_rectangles: None,
+            expeditions_state: None,
+            game_result: None,
+        };
+        let mut changes = Box::pin(watch(api.clone(), config));
+
+        assert_eq!(changes.next().await.unwrap()?, Change::Deck(menus));
+        assert!(changes.next().await.unwrap().is_err());
+        assert_eq!(
+            changes.next().await.unwrap()?,
+            Change::Deck(in_game.clone())
+        );
+        let unchanged = tokio::time::timeout(Duration::from_millis(20), changes.next()).await;
+        assert!(unchanged.is_err());
+        assert_eq!(api.get_static_decklist().await?, in_game);
+
+        assert!(matches!(
+            api.get_game_result().await,
+            Err(crate::Error::ConnectionRefused { source: None, .. })
+        ));
+
+        Ok(())
+    }
+}
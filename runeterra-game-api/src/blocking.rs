@@ -0,0 +1,124 @@
+//! A client for synchronous code, enabled by the `blocking` feature.
+//!
+//! Has the same endpoints, types and errors as the async [`Client`](crate::Client), without
+//! needing a runtime.
+//!
+//! ```no_run
+//! use runeterra_game_api::{blocking::Client, DEFAULT_PORT};
+//!
+//! let client = Client::new(DEFAULT_PORT);
+//! let result = client.get_static_decklist();
+//! dbg!(&result);
+//! ```
+
+use crate::client::{check_status, decode, endpoint_url};
+use crate::{
+    ClientBuilder, ExpeditionsState, GameResult, PositionalRectangles, RetryPolicy, StaticDecklist,
+};
+use reqwest::Url;
+use serde::de::DeserializeOwned;
+use std::thread;
+
+#[derive(Debug, Clone)]
+pub struct Client {
+    pub(crate) session: reqwest::blocking::Client,
+    pub(crate) base_url: Url,
+    pub(crate) retry: RetryPolicy,
+}
+
+impl Client {
+    /// A client for the game on `localhost` at `port`, see [`ClientBuilder::build_blocking`]
+    /// for other settings.
+    ///
+    /// Panics if the HTTP client can't be created.
+    pub fn new(port: u16) -> Self {
+        ClientBuilder::new()
+            .port(port)
+            .build_blocking()
+            .expect("Failed to create the HTTP client")
+    }
+
+    fn get<T: DeserializeOwned>(&self, endpoint: &str) -> Result<T, crate::Error> {
+        let mut retry = 0;
+        loop {
+            match self.try_get(endpoint) {
+                Err(err) if retry < self.retry.max_retries && err.is_transient() => {
+                    thread::sleep(self.retry.backoff(retry));
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn try_get<T: DeserializeOwned>(&self, endpoint: &str) -> Result<T, crate::Error> {
+        let response = self
+            .session
+            .get(endpoint_url(&self.base_url, endpoint)?)
+            .send()?;
+        check_status(endpoint, response.status())?;
+        decode(endpoint, &response.bytes()?)
+    }
+
+    pub fn get_static_decklist(&self) -> Result<StaticDecklist, crate::Error> {
+        self.get("/static-decklist")
+    }
+
+    pub fn get_positional_rectangles(&self) -> Result<PositionalRectangles, crate::Error> {
+        self.get("/positional-rectangles")
+    }
+
+    pub fn get_expeditions_state(&self) -> Result<ExpeditionsState, crate::Error> {
+        self.get("/expeditions-state")
+    }
+
+    pub fn get_game_result(&self) -> Result<GameResult, crate::Error> {
+        self.get("/game-result")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ClientBuilder, GameResult};
+
+    #[test]
+    fn test_game_result() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let body = GameResult {
+            game_id: 7,
+            local_player_won: true,
+        };
+
+        let m = mockito::mock("GET", "/game-result")
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&body)?)
+            .create();
+
+        let client = ClientBuilder::new()
+            .base_url(mockito::server_url())
+            .build_blocking()?;
+        assert_eq!(client.get_game_result()?, body);
+        m.assert();
+
+        let m = mockito::mock("GET", "/game-result")
+            .with_status(404)
+            .create();
+        assert!(client.get_game_result().is_err());
+        m.assert();
+
+        let m = mockito::mock("GET", "/prefix/game-result")
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&body)?)
+            .create();
+        let client = ClientBuilder::new()
+            .base_url(format!("{}/prefix", mockito::server_url()))
+            .build_blocking()?;
+        assert_eq!(client.get_game_result()?, body);
+        m.assert();
+        assert!(ClientBuilder::new()
+            .base_url("mailto:x")
+            .build_blocking()
+            .is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+use crate::{Client, DEFAULT_PORT};
+use reqwest::Url;
+use std::net::Ipv6Addr;
+use std::time::Duration;
+
+/// How requests failing with a transient error are retried.
+///
+/// Timeouts, failed connections and `5xx` responses are transient, the delay before retry `n`
+/// is `initial_backoff * 2^n`, capped at `max_backoff`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Doesn't retry, the default.
+    pub fn none() -> Self {
+        RetryPolicy::new(0)
+    }
+
+    pub fn new(max_retries: u32) -> Self {
+        RetryPolicy {
+            max_retries,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(2),
+        }
+    }
+
+    pub(crate) fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.checked_pow(retry).unwrap_or(u32::MAX);
+        self.initial_backoff
+            .checked_mul(factor)
+            .map_or(self.max_backoff, |backoff| backoff.min(self.max_backoff))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::none()
+    }
+}
+
+/// Configures a [`Client`], by default for the game on `http://localhost:21337`.
+#[derive(Debug, Clone)]
+pub struct ClientBuilder {
+    host: String,
+    port: u16,
+    base_url: Option<String>,
+    timeout: Option<Duration>,
+    retry: RetryPolicy,
+}
+
+impl Default for ClientBuilder {
+    fn default() -> Self {
+        ClientBuilder {
+            host: "localhost".to_string(),
+            port: DEFAULT_PORT,
+            base_url: None,
+            timeout: Some(Duration::from_secs(5)),
+            retry: RetryPolicy::none(),
+        }
+    }
+}
+
+impl ClientBuilder {
+    pub fn new() -> Self {
+        ClientBuilder::default()
+    }
+
+    pub fn host(mut self, host: impl Into<String>) -> Self {
+        self.host = host.into();
+        self
+    }
+
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Sends requests to `base_url` instead of `http://<host>:<port>`, e.g. a mock server in
+    /// tests.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Time allowed for each request, `None` to wait forever.
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    fn parse_base_url(&self) -> Result<Url, crate::Error> {
+        let base_url = match &self.base_url {
+            Some(base_url) => base_url.clone(),
+            // IPv6 literals such as `::1` are bracketed in URLs.
+            None if self.host.parse::<Ipv6Addr>().is_ok() => {
+                format!("http://[{}]:{}", self.host, self.port)
+            }
+            None => format!("http://{}:{}", self.host, self.port),
+        };
+        let invalid = |url, source| crate::Error::InvalidBaseUrl { url, source };
+
+        let mut url =
+            Url::parse(&base_url).map_err(|source| invalid(base_url.clone(), Some(source)))?;
+        if url.cannot_be_a_base() || !matches!(url.scheme(), "http" | "https") {
+            return Err(invalid(base_url, None));
+        }
+        // Endpoints are joined as relative paths, which would replace a last segment without `/`.
+        if !url.path().ends_with('/') {
+            let path = format!("{}/", url.path());
+            url.set_path(&path);
+        }
+        Ok(url)
+    }
+
+    pub fn build(self) -> Result<Client, crate::Error> {
+        let base_url = self.parse_base_url()?;
+
+        let mut session = reqwest::Client::builder();
+        if let Some(timeout) = self.timeout {
+            session = session.timeout(timeout);
+        }
+
+        Ok(Client {
+            session: session.build()?,
+            base_url,
+            retry: self.retry,
+        })
+    }
+
+    /// Builds a [`blocking::Client`](crate::blocking::Client) with the same settings.
+    #[cfg(feature = "blocking")]
+    pub fn build_blocking(self) -> Result<crate::blocking::Client, crate::Error> {
+        let base_url = self.parse_base_url()?;
+
+        let session = reqwest::blocking::Client::builder()
+            .timeout(self.timeout)
+            .build()?;
+
+        Ok(crate::blocking::Client {
+            session,
+            base_url,
+            retry: self.retry,
+        })
+    }
+}
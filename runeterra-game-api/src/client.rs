@@ -1,17 +1,16 @@
+use crate::{ClientBuilder, RetryPolicy};
 use reqwest::Url;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[cfg(test)]
-use mockito;
-
-#[allow(dead_code)]
 pub const DEFAULT_PORT: u16 = 21337;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Client {
-    session: reqwest::Client,
-    base_url: Url,
+    pub(crate) session: reqwest::Client,
+    pub(crate) base_url: Url,
+    pub(crate) retry: RetryPolicy,
 }
 
 /// The player's current deck in an active game.
@@ -19,7 +18,7 @@ pub struct Client {
 /// See official ['API'] for more information.
 ///
 /// ['API']: https://developer.riotgames.com/docs/lor#game-client-api_active-deck
-#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct StaticDecklist {
     pub deck_code: Option<String>,
@@ -27,7 +26,7 @@ pub struct StaticDecklist {
 }
 
 /// Screen information.
-#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct Screen {
     pub screen_width: u16,
@@ -35,7 +34,7 @@ pub struct Screen {
 }
 
 /// Information about a rectangle on screen.
-#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct Rectangle {
     #[serde(rename = "GameID")]
@@ -53,26 +52,128 @@ pub struct Rectangle {
 /// See official ['API'] for more information.
 ///
 /// ['API']: https://developer.riotgames.com/docs/lor#game-client-api_card-positions
-#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct PositionalRectangles {
     pub player_name: Option<String>,
     pub opponent_name: Option<String>,
-    pub game_state: String,
+    pub game_state: GameState,
     pub screen: Screen,
     pub rectangles: Vec<Rectangle>,
 }
 
+/// Whether the player is in the menus or in a game.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum GameState {
+    Menus,
+    InProgress,
+    /// A state not known to this crate.
+    Other(String),
+}
+
+impl GameState {
+    pub fn as_str(&self) -> &str {
+        match self {
+            GameState::Menus => "Menus",
+            GameState::InProgress => "InProgress",
+            GameState::Other(state) => state,
+        }
+    }
+
+    pub fn in_game(&self) -> bool {
+        *self == GameState::InProgress
+    }
+
+    pub fn in_menus(&self) -> bool {
+        *self == GameState::Menus
+    }
+}
+
+impl From<String> for GameState {
+    fn from(state: String) -> Self {
+        match state.as_str() {
+            "Menus" => GameState::Menus,
+            "InProgress" => GameState::InProgress,
+            _ => GameState::Other(state),
+        }
+    }
+}
+
+impl From<GameState> for String {
+    fn from(state: GameState) -> Self {
+        match state {
+            GameState::Other(state) => state,
+            state => state.as_str().to_string(),
+        }
+    }
+}
+
+/// The stage of the player's Expedition.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum ExpeditionState {
+    Inactive,
+    Offscreen,
+    /// Picking cards for the deck.
+    Picking,
+    /// Swapping cards of the deck after a game.
+    Swapping,
+    /// A state not known to this crate, including the API's own `Other`.
+    Other(String),
+}
+
+impl ExpeditionState {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ExpeditionState::Inactive => "Inactive",
+            ExpeditionState::Offscreen => "Offscreen",
+            ExpeditionState::Picking => "Picking",
+            ExpeditionState::Swapping => "Swapping",
+            ExpeditionState::Other(state) => state,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        *self != ExpeditionState::Inactive
+    }
+
+    pub fn drafting(&self) -> bool {
+        matches!(self, ExpeditionState::Picking | ExpeditionState::Swapping)
+    }
+}
+
+impl From<String> for ExpeditionState {
+    fn from(state: String) -> Self {
+        match state.as_str() {
+            "Inactive" => ExpeditionState::Inactive,
+            "Offscreen" => ExpeditionState::Offscreen,
+            "Picking" => ExpeditionState::Picking,
+            "Swapping" => ExpeditionState::Swapping,
+            _ => ExpeditionState::Other(state),
+        }
+    }
+}
+
+impl From<ExpeditionState> for String {
+    fn from(state: ExpeditionState) -> Self {
+        match state {
+            ExpeditionState::Other(state) => state,
+            state => state.as_str().to_string(),
+        }
+    }
+}
+
 /// The player's drafted cards during an Expedition.
 ///
 /// See official ['API'] for more information.
 ///
 /// ['API']: https://developer.riotgames.com/docs/lor#game-client-api_expeditions
-#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct ExpeditionsState {
     pub is_active: bool,
-    pub state: String,
+    pub state: ExpeditionState,
     pub record: Option<Vec<String>>,
     // Not implemented, type unknown
     pub draft_picks: Option<Vec<String>>,
@@ -87,7 +188,7 @@ pub struct ExpeditionsState {
 /// See official ['API'] for more information.
 ///
 /// ['API']: https://developer.riotgames.com/docs/lor#game-client-api_game-result
-#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct GameResult {
     #[serde(rename = "GameID")]
@@ -95,71 +196,145 @@ pub struct GameResult {
     pub local_player_won: bool,
 }
 
+/// What [`Client::status`] found at the other end of the connection.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ConnectionStatus {
+    /// Nothing accepted the connection.
+    GameNotRunning,
+    /// The game didn't answer in time.
+    Timeout,
+    /// The game answered with an error status code.
+    HttpError(u16),
+    /// The answer wasn't a Game Client API response.
+    InvalidResponse,
+    InMenus,
+    InGame,
+    /// The game reported a state not known to this crate.
+    OtherState(String),
+}
+
 impl Client {
-    pub fn new(#[cfg(not(test))] port: u16) -> Self {
-        #[cfg(test)]
-        let url = &mockito::server_url();
+    /// A client for the game on `localhost` at `port`, see [`ClientBuilder`] for other settings.
+    ///
+    /// Panics if the HTTP client can't be created, use [`ClientBuilder::build`] to handle that.
+    pub fn new(port: u16) -> Self {
+        ClientBuilder::new()
+            .port(port)
+            .build()
+            .expect("Failed to create the HTTP client")
+    }
 
-        #[cfg(not(test))]
-        let url = format!("http://localhost:{}", port);
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
 
-        Client {
-            session: reqwest::Client::builder().build().unwrap(),
-            base_url: Url::parse(&url).unwrap(),
+    async fn get<T: DeserializeOwned>(&self, endpoint: &str) -> Result<T, crate::Error> {
+        let mut retry = 0;
+        loop {
+            match self.try_get(endpoint).await {
+                Err(err) if retry < self.retry.max_retries && err.is_transient() => {
+                    tokio::time::delay_for(self.retry.backoff(retry)).await;
+                    retry += 1;
+                }
+                result => return result,
+            }
         }
     }
 
-    pub async fn get_static_decklist(&self) -> Result<StaticDecklist, crate::Error> {
-        Ok(self
+    async fn try_get<T: DeserializeOwned>(&self, endpoint: &str) -> Result<T, crate::Error> {
+        let response = self
             .session
-            .get(self.base_url.join("/static-decklist").unwrap())
+            .get(endpoint_url(&self.base_url, endpoint)?)
             .send()
-            .await?
-            .json::<StaticDecklist>()
-            .await?)
+            .await?;
+        check_status(endpoint, response.status())?;
+        decode(endpoint, &response.bytes().await?)
+    }
+
+    pub async fn get_static_decklist(&self) -> Result<StaticDecklist, crate::Error> {
+        self.get("/static-decklist").await
     }
 
     pub async fn get_positional_rectangles(&self) -> Result<PositionalRectangles, crate::Error> {
-        Ok(self
-            .session
-            .get(self.base_url.join("/positional-rectangles").unwrap())
-            .send()
-            .await?
-            .json::<PositionalRectangles>()
-            .await?)
+        self.get("/positional-rectangles").await
     }
 
     pub async fn get_expeditions_state(&self) -> Result<ExpeditionsState, crate::Error> {
-        Ok(self
-            .session
-            .get(self.base_url.join("/expeditions-state").unwrap())
-            .send()
-            .await?
-            .json::<ExpeditionsState>()
-            .await?)
+        self.get("/expeditions-state").await
     }
 
     pub async fn get_game_result(&self) -> Result<GameResult, crate::Error> {
-        Ok(self
-            .session
-            .get(self.base_url.join("/game-result").unwrap())
-            .send()
-            .await?
-            .json::<GameResult>()
-            .await?)
+        self.get("/game-result").await
+    }
+
+    /// Classifies whether the game can be reached and what it is doing, never failing.
+    ///
+    /// The game doesn't listen on its port at all while the API is disabled in its settings, so
+    /// that case is reported as [`ConnectionStatus::GameNotRunning`] too.
+    pub async fn status(&self) -> ConnectionStatus {
+        let rectangles = self
+            .try_get::<PositionalRectangles>("/positional-rectangles")
+            .await;
+        match rectangles {
+            Ok(rectangles) => match rectangles.game_state {
+                GameState::Menus => ConnectionStatus::InMenus,
+                GameState::InProgress => ConnectionStatus::InGame,
+                GameState::Other(state) => ConnectionStatus::OtherState(state),
+            },
+            Err(crate::Error::Timeout { .. }) => ConnectionStatus::Timeout,
+            Err(crate::Error::ConnectionRefused { .. }) => ConnectionStatus::GameNotRunning,
+            Err(crate::Error::Status { status, .. }) => ConnectionStatus::HttpError(status),
+            Err(_) => ConnectionStatus::InvalidResponse,
+        }
     }
 }
 
+/// The URL of `endpoint`, e.g. `/game-result`, below `base_url` including any path prefix.
+pub(crate) fn endpoint_url(base_url: &Url, endpoint: &str) -> Result<Url, crate::Error> {
+    base_url
+        .join(endpoint.trim_start_matches('/'))
+        .map_err(|source| crate::Error::InvalidBaseUrl {
+            url: base_url.to_string(),
+            source: Some(source),
+        })
+}
+
+pub(crate) fn check_status(
+    endpoint: &str,
+    status: reqwest::StatusCode,
+) -> Result<(), crate::Error> {
+    if status.is_success() {
+        Ok(())
+    } else {
+        Err(crate::Error::Status {
+            endpoint: endpoint.to_string(),
+            status: status.as_u16(),
+        })
+    }
+}
+
+pub(crate) fn decode<T: DeserializeOwned>(endpoint: &str, body: &[u8]) -> Result<T, crate::Error> {
+    serde_json::from_slice(body).map_err(|err| crate::Error::decode(endpoint, body, err))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
-        Client, ExpeditionsState, GameResult, PositionalRectangles, Rectangle, Screen,
-        StaticDecklist,
+        Client, ConnectionStatus, ExpeditionState, ExpeditionsState, GameResult, GameState,
+        PositionalRectangles, Rectangle, Screen, StaticDecklist,
     };
-    use mockito;
+    use crate::{ClientBuilder, RetryPolicy};
+    use std::time::Duration;
+
+    fn client() -> Client {
+        ClientBuilder::new()
+            .base_url(mockito::server_url())
+            .build()
+            .unwrap()
+    }
 
     #[tokio::test]
-    async fn test_static_decklist() -> std::result::Result<(), crate::Error> {
+    async fn test_static_decklist() -> std::result::Result<(), Box<dyn std::error::Error>> {
         let body = StaticDecklist {
             deck_code: Some(
                 "CEAAECABAMGA6EYXEYVS4NYIAECQCGY5FAVTCMRVAICACAYCBELDGBABAURCMKJW".to_string(),
@@ -202,7 +377,7 @@ mod tests {
             .with_body(serde_json::to_string(&body)?)
             .create();
 
-        let client = Client::new();
+        let client = client();
         let res = client.get_static_decklist().await?;
 
         assert_eq!(body, res);
@@ -212,11 +387,11 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_positional_rectangles() -> std::result::Result<(), crate::Error> {
+    async fn test_positional_rectangles() -> std::result::Result<(), Box<dyn std::error::Error>> {
         let body = PositionalRectangles {
             player_name: Some("Player One".to_string()),
             opponent_name: Some("Player Two".to_string()),
-            game_state: "InProgress".to_string(),
+            game_state: GameState::InProgress,
             screen: Screen {
                 screen_width: 1920,
                 screen_height: 1080,
@@ -237,7 +412,7 @@ mod tests {
             .with_body(serde_json::to_string(&body)?)
             .create();
 
-        let client = Client::new();
+        let client = client();
         let res = client.get_positional_rectangles().await?;
 
         assert_eq!(body, res);
@@ -247,10 +422,10 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_expeditions_state() -> std::result::Result<(), crate::Error> {
+    async fn test_expeditions_state() -> std::result::Result<(), Box<dyn std::error::Error>> {
         let body = ExpeditionsState {
             is_active: false,
-            state: "Inactive".to_string(),
+            state: ExpeditionState::Inactive,
             record: None,
             draft_picks: None,
             deck: None,
@@ -264,7 +439,7 @@ mod tests {
             .with_body(serde_json::to_string(&body)?)
             .create();
 
-        let client = Client::new();
+        let client = client();
         let res = client.get_expeditions_state().await?;
 
         assert_eq!(body, res);
@@ -274,7 +449,7 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_game_result() -> std::result::Result<(), crate::Error> {
+    async fn test_game_result() -> std::result::Result<(), Box<dyn std::error::Error>> {
         let body = GameResult {
             game_id: -1,
             local_player_won: false,
@@ -285,7 +460,7 @@ mod tests {
             .with_body(serde_json::to_string(&body)?)
             .create();
 
-        let client = Client::new();
+        let client = client();
         let res = client.get_game_result().await?;
 
         assert_eq!(body, res);
@@ -293,4 +468,246 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_watch() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        use crate::{Change, WatchConfig};
+        use futures::StreamExt;
+        use std::time::Duration;
+
+        let first = StaticDecklist {
+            deck_code: None,
+            cards_in_deck: None,
+        };
+        let second = StaticDecklist {
+            deck_code: Some(
+                "CEAAECABAMGA6EYXEYVS4NYIAECQCGY5FAVTCMRVAICACAYCBELDGBABAURCMKJW".to_string(),
+            ),
+            cards_in_deck: Some([("01NX012".to_string(), 2)].iter().cloned().collect()),
+        };
+
+        // Served three times before the second deck, which must only be yielded once.
+        let m1 = mockito::mock("GET", "/static-decklist")
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&first)?)
+            .expect(3)
+            .create();
+        let m2 = mockito::mock("GET", "/static-decklist")
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&second)?)
+            .expect_at_least(1)
+            .create();
+
+        let config = WatchConfig {
+            static_decklist: Some(Duration::from_millis(10)),
+            positional_rectangles: None,
+            expeditions_state: None,
+            game_result: None,
+        };
+        let mut changes = Box::pin(client().watch(config));
+
+        assert_eq!(changes.next().await.unwrap()?, Change::Deck(first));
+        assert_eq!(changes.next().await.unwrap()?, Change::Deck(second));
+        let unchanged = tokio::time::timeout(Duration::from_millis(100), changes.next()).await;
+        assert!(unchanged.is_err());
+        m1.assert();
+        m2.assert();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_watch_game_result() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        use crate::{Change, WatchConfig};
+        use futures::StreamExt;
+        use std::time::Duration;
+
+        let previous = GameResult {
+            game_id: 1,
+            local_player_won: false,
+        };
+        let ended = GameResult {
+            game_id: 2,
+            local_player_won: true,
+        };
+
+        // The result of a game that ended before watching isn't yielded.
+        let m1 = mockito::mock("GET", "/game-result")
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&previous)?)
+            .expect(2)
+            .create();
+        let m2 = mockito::mock("GET", "/game-result")
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&ended)?)
+            .expect_at_least(1)
+            .create();
+
+        let config = WatchConfig {
+            static_decklist: None,
+            positional_rectangles: None,
+            expeditions_state: None,
+            game_result: Some(Duration::from_millis(10)),
+        };
+        let mut changes = Box::pin(client().watch(config));
+
+        assert_eq!(changes.next().await.unwrap()?, Change::GameResult(ended));
+        m1.assert();
+        m2.assert();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_states() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let state: GameState = serde_json::from_str("\"InProgress\"")?;
+        assert!(state.in_game() && !state.in_menus());
+        let state: GameState = serde_json::from_str("\"Loading\"")?;
+        assert_eq!(state, GameState::Other("Loading".to_string()));
+        assert_eq!(serde_json::to_string(&state)?, "\"Loading\"");
+
+        let state: ExpeditionState = serde_json::from_str("\"Swapping\"")?;
+        assert!(state.drafting() && state.is_active());
+        let state: ExpeditionState = serde_json::from_str("\"Inactive\"")?;
+        assert!(!state.drafting() && !state.is_active());
+        assert_eq!(serde_json::to_string(&state)?, "\"Inactive\"");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_status() {
+        let client = client();
+
+        let m = mockito::mock("GET", "/positional-rectangles")
+            .with_status(503)
+            .create();
+        assert_eq!(client.status().await, ConnectionStatus::HttpError(503));
+        drop(m);
+
+        let m = mockito::mock("GET", "/positional-rectangles")
+            .with_header("content-type", "application/json")
+            .with_body("<html></html>")
+            .create();
+        assert_eq!(client.status().await, ConnectionStatus::InvalidResponse);
+        drop(m);
+
+        let m = mockito::mock("GET", "/positional-rectangles")
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"PlayerName":null,"OpponentName":null,"GameState":"Menus","Screen":{"ScreenWidth":1920,"ScreenHeight":1080},"Rectangles":[]}"#,
+            )
+            .create();
+        assert_eq!(client.status().await, ConnectionStatus::InMenus);
+        drop(m);
+
+        let refused = ClientBuilder::new().port(1).build().unwrap();
+        assert_eq!(refused.status().await, ConnectionStatus::GameNotRunning);
+    }
+
+    #[tokio::test]
+    async fn test_retry() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let body = GameResult {
+            game_id: 3,
+            local_player_won: true,
+        };
+
+        let unavailable = mockito::mock("GET", "/game-result")
+            .with_status(503)
+            .expect(2)
+            .create();
+        let m = mockito::mock("GET", "/game-result")
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&body)?)
+            .create();
+
+        let retry = RetryPolicy {
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(10),
+        };
+        let client = ClientBuilder::new()
+            .base_url(mockito::server_url())
+            .retry(retry)
+            .build()?;
+        assert_eq!(client.get_game_result().await?, body);
+        unavailable.assert();
+        m.assert();
+
+        assert!(ClientBuilder::new().base_url("localhost").build().is_err());
+        for base_url in &["mailto:x", "data:text/plain,x", "ftp://localhost"] {
+            let err = ClientBuilder::new()
+                .base_url(*base_url)
+                .build()
+                .unwrap_err();
+            assert!(matches!(
+                err,
+                crate::Error::InvalidBaseUrl { source: None, .. }
+            ));
+        }
+        for host in &["::1", "[::1]"] {
+            let client = ClientBuilder::new().host(*host).build()?;
+            assert_eq!(client.base_url.as_str(), "http://[::1]:21337/");
+        }
+
+        let m = mockito::mock("GET", "/prefix/game-result")
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&body)?)
+            .create();
+        let client = ClientBuilder::new()
+            .base_url(format!("{}/prefix", mockito::server_url()))
+            .build()?;
+        assert_eq!(client.get_game_result().await?, body);
+        m.assert();
+        assert_eq!(RetryPolicy::new(8).backoff(6), Duration::from_secs(2));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_errors() {
+        use std::error::Error as _;
+
+        let client = client();
+
+        let m = mockito::mock("GET", "/game-result")
+            .with_status(404)
+            .create();
+        match client.get_game_result().await {
+            Err(crate::Error::Status { endpoint, status }) => {
+                assert_eq!((endpoint.as_str(), status), ("/game-result", 404))
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        drop(m);
+
+        let body = format!("{{\"GameID\": \"{}\"}}", "x".repeat(300));
+        let m = mockito::mock("GET", "/game-result")
+            .with_header("content-type", "application/json")
+            .with_body(&body)
+            .create();
+        let err = client.get_game_result().await.unwrap_err();
+        assert!(err.source().is_some());
+        match err {
+            crate::Error::Decode { endpoint, body, .. } => {
+                assert_eq!(endpoint, "/game-result");
+                assert!(body.starts_with("{\"GameID\": \"xxx"));
+                assert!(body.ends_with("...") && body.len() < 300);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        drop(m);
+
+        let refused = ClientBuilder::new().port(1).build().unwrap();
+        let err = refused.get_game_result().await.unwrap_err();
+        assert!(err.is_transient() && err.source().is_some());
+        assert!(matches!(err, crate::Error::ConnectionRefused { .. }));
+
+        let err = ClientBuilder::new()
+            .base_url("localhost")
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, crate::Error::InvalidBaseUrl { .. }));
+        assert!(err.source().is_some());
+    }
 }
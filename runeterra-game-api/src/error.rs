@@ -1,38 +1,89 @@
-use core::fmt;
-use serde::export::Formatter;
+use thiserror::Error;
 
-#[derive(Debug)]
+/// Longest part of an invalid response body kept in [`Error::Decode`].
+const SNIPPET_LEN: usize = 200;
+
+#[derive(Debug, Error)]
 pub enum Error {
+    /// Nothing accepted the connection, the game isn't running or its API is disabled.
+    ///
+    /// `source` is `None` when replayed from a recorded session, as for [`Error::Timeout`].
+    #[error("Could not connect to {url}, is the game running with its API enabled?")]
+    ConnectionRefused {
+        url: String,
+        #[source]
+        source: Option<reqwest::Error>,
+    },
+    #[error("Request to {url} timed out")]
+    Timeout {
+        url: String,
+        #[source]
+        source: Option<reqwest::Error>,
+    },
+    /// The game answered with a status other than `2xx`.
+    #[error("{endpoint} responded with status {status}")]
+    Status { endpoint: String, status: u16 },
+    /// The body isn't the expected JSON, `body` is its start.
+    #[error("Invalid response from {endpoint}: {source}, body: {body}")]
+    Decode {
+        endpoint: String,
+        body: String,
+        #[source]
+        source: serde_json::Error,
+    },
+    /// Not an `http` or `https` URL endpoints can be appended to, `source` is set if it didn't
+    /// parse at all.
+    #[error("Invalid base URL {url}")]
+    InvalidBaseUrl {
+        url: String,
+        #[source]
+        source: Option<url::ParseError>,
+    },
+    /// Any other failure of the HTTP client.
+    #[error(transparent)]
     Http(reqwest::Error),
-    ResponseParse(serde_json::error::Error),
 }
 
-impl fmt::Display for Error {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match *self {
-            Error::Http(ref err) => reqwest::Error::fmt(err, f),
-            Error::ResponseParse(ref err) => serde_json::error::Error::fmt(err, f),
+impl Error {
+    pub(crate) fn decode(endpoint: &str, body: &[u8], source: serde_json::Error) -> Self {
+        let mut snippet =
+            String::from_utf8_lossy(&body[..body.len().min(SNIPPET_LEN)]).into_owned();
+        if body.len() > SNIPPET_LEN {
+            snippet.push_str("...");
+        }
+        Error::Decode {
+            endpoint: endpoint.to_string(),
+            body: snippet,
+            source,
         }
     }
-}
 
-impl std::error::Error for Error {
-    fn description(&self) -> &str {
-        match *self {
-            Error::Http(ref err) => err.description(),
-            Error::ResponseParse(ref err) => err.description(),
+    /// Whether the request may succeed if retried: timeouts, refused connections and `5xx`
+    /// statuses.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Error::ConnectionRefused { .. } | Error::Timeout { .. } => true,
+            Error::Status { status, .. } => *status >= 500,
+            _ => false,
         }
     }
 }
 
 impl From<reqwest::Error> for Error {
-    fn from(err: reqwest::Error) -> Self {
-        Error::Http(err)
-    }
-}
-
-impl From<serde_json::error::Error> for Error {
-    fn from(err: serde_json::error::Error) -> Self {
-        Error::ResponseParse(err)
+    fn from(source: reqwest::Error) -> Self {
+        let url = source.url().map(ToString::to_string).unwrap_or_default();
+        if source.is_timeout() {
+            Error::Timeout {
+                url,
+                source: Some(source),
+            }
+        } else if source.is_connect() {
+            Error::ConnectionRefused {
+                url,
+                source: Some(source),
+            }
+        } else {
+            Error::Http(source)
+        }
     }
 }
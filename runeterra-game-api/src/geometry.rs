@@ -0,0 +1,201 @@
+use crate::{PositionalRectangles, Rectangle, Screen};
+
+/// A rectangle with its origin in the top-left corner of the screen, `y` growing downwards.
+///
+/// In pixels, or in `0..1` of the screen size once [`normalized`](Rect::normalized).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Rect {
+    pub fn new(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    pub fn right(&self) -> f64 {
+        self.x + self.width
+    }
+
+    pub fn bottom(&self) -> f64 {
+        self.y + self.height
+    }
+
+    /// Converts from pixels of `screen` to resolution-independent `0..1` coordinates.
+    ///
+    /// `None` if the screen has no area, which would make every coordinate infinite or `NaN`.
+    pub fn normalized(&self, screen: &Screen) -> Option<Rect> {
+        let (width, height) = screen_size(screen);
+        if width == 0.0 || height == 0.0 {
+            return None;
+        }
+        Some(Rect::new(
+            self.x / width,
+            self.y / height,
+            self.width / width,
+            self.height / height,
+        ))
+    }
+
+    /// Converts from `0..1` coordinates to pixels of `screen`.
+    pub fn denormalized(&self, screen: &Screen) -> Rect {
+        let (width, height) = screen_size(screen);
+        Rect::new(
+            self.x * width,
+            self.y * height,
+            self.width * width,
+            self.height * height,
+        )
+    }
+
+    /// Converts from pixels of `from` to pixels of `to`, e.g. an overlay window of another size.
+    ///
+    /// `None` if `from` has no area, see [`Rect::normalized`].
+    pub fn rescaled(&self, from: &Screen, to: &Screen) -> Option<Rect> {
+        Some(self.normalized(from)?.denormalized(to))
+    }
+
+    /// Whether the point is inside, including the top and left edges but not the others.
+    pub fn contains_point(&self, x: f64, y: f64) -> bool {
+        x >= self.x && x < self.right() && y >= self.y && y < self.bottom()
+    }
+
+    pub fn contains(&self, other: &Rect) -> bool {
+        other.x >= self.x
+            && other.right() <= self.right()
+            && other.y >= self.y
+            && other.bottom() <= self.bottom()
+    }
+
+    /// The overlapping area, `None` if the rectangles don't overlap or only touch.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = self.right().min(other.right());
+        let bottom = self.bottom().min(other.bottom());
+        if x < right && y < bottom {
+            Some(Rect::new(x, y, right - x, bottom - y))
+        } else {
+            None
+        }
+    }
+
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.intersection(other).is_some()
+    }
+}
+
+fn screen_size(screen: &Screen) -> (f64, f64) {
+    (
+        f64::from(screen.screen_width),
+        f64::from(screen.screen_height),
+    )
+}
+
+impl Rectangle {
+    /// The rectangle in pixels with a top-left origin.
+    ///
+    /// The game reports `top_left_y` upwards from the bottom edge of `screen`.
+    pub fn to_rect(&self, screen: &Screen) -> Rect {
+        Rect::new(
+            f64::from(self.top_left_x),
+            f64::from(screen.screen_height) - f64::from(self.top_left_y),
+            f64::from(self.width),
+            f64::from(self.height),
+        )
+    }
+}
+
+impl PositionalRectangles {
+    /// Every rectangle in pixels with a top-left origin, see [`Rectangle::to_rect`].
+    pub fn rects(&self) -> impl Iterator<Item = (&Rectangle, Rect)> {
+        self.rectangles
+            .iter()
+            .map(move |rectangle| (rectangle, rectangle.to_rect(&self.screen)))
+    }
+
+    /// The card at the point, in pixels of the game screen with a top-left origin.
+    ///
+    /// Rectangles overlap in the hand, the last one listed is assumed to be on top.
+    pub fn card_at(&self, x: f64, y: f64) -> Option<&Rectangle> {
+        self.rects()
+            .filter(|(_, rect)| rect.contains_point(x, y))
+            .map(|(rectangle, _)| rectangle)
+            .last()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{GameState, PositionalRectangles, Rect, Rectangle, Screen};
+
+    fn rectangle(card_id: i32, top_left_x: u16, top_left_y: u16) -> Rectangle {
+        Rectangle {
+            card_id,
+            card_code: "01NX038".to_string(),
+            top_left_x,
+            top_left_y,
+            width: 100,
+            height: 150,
+            local_player: true,
+        }
+    }
+
+    #[test]
+    fn test_geometry() {
+        let screen = Screen {
+            screen_width: 1920,
+            screen_height: 1080,
+        };
+        let window = Screen {
+            screen_width: 960,
+            screen_height: 540,
+        };
+
+        let rect = rectangle(1, 200, 300).to_rect(&screen);
+        assert_eq!(rect, Rect::new(200.0, 780.0, 100.0, 150.0));
+        assert_eq!(rect.bottom(), 930.0);
+        assert_eq!(
+            rect.normalized(&screen).unwrap().denormalized(&screen),
+            Rect::new(200.0, 780.0, 100.0, 150.0)
+        );
+        assert_eq!(
+            rect.rescaled(&screen, &window),
+            Some(Rect::new(100.0, 390.0, 50.0, 75.0))
+        );
+        let empty = Screen {
+            screen_width: 0,
+            screen_height: 1080,
+        };
+        assert_eq!(rect.normalized(&empty), None);
+        assert_eq!(rect.rescaled(&empty, &window), None);
+
+        let other = Rect::new(250.0, 700.0, 100.0, 100.0);
+        assert_eq!(
+            rect.intersection(&other),
+            Some(Rect::new(250.0, 780.0, 50.0, 20.0))
+        );
+        assert!(!rect.intersects(&Rect::new(300.0, 780.0, 10.0, 10.0)));
+        assert!(rect.contains(&Rect::new(210.0, 790.0, 10.0, 10.0)));
+        assert!(!rect.contains(&other));
+
+        let rectangles = PositionalRectangles {
+            player_name: None,
+            opponent_name: None,
+            game_state: GameState::InProgress,
+            screen,
+            rectangles: vec![rectangle(1, 200, 300), rectangle(2, 250, 300)],
+        };
+        assert_eq!(rectangles.card_at(220.0, 800.0).unwrap().card_id, 1);
+        assert_eq!(rectangles.card_at(260.0, 800.0).unwrap().card_id, 2);
+        assert!(rectangles.card_at(220.0, 300.0).is_none());
+    }
+}
@@ -13,8 +13,22 @@
 //! }
 //!```
 //!
+mod api;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod builder;
 mod client;
 mod error;
+mod geometry;
+mod scenario;
+mod session;
+mod watch;
 
+pub use self::api::*;
+pub use self::builder::*;
 pub use self::client::*;
 pub use self::error::*;
+pub use self::geometry::*;
+pub use self::scenario::*;
+pub use self::session::*;
+pub use self::watch::*;
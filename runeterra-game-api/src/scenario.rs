@@ -0,0 +1,422 @@
+use crate::{
+    ExpeditionState, ExpeditionsState, GameResult, GameState, PositionalRectangles, Screen,
+    StaticDecklist,
+};
+use serde::de::Error as _;
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, Write};
+use std::time::Duration;
+
+/// Responses of the game changing at a point of a [`Scenario`].
+///
+/// Endpoints left out keep answering what they answered before.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Event {
+    /// Seconds since the start of the scenario.
+    pub at: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub static_decklist: Option<StaticDecklist>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub positional_rectangles: Option<PositionalRectangles>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expeditions_state: Option<ExpeditionsState>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub game_result: Option<GameResult>,
+    /// Endpoints failing from this event on, until a later event has a response for them.
+    #[serde(default, skip_serializing_if = "Failures::is_empty")]
+    pub failures: Failures,
+}
+
+/// How a request failed instead of getting a response, as recorded in a [`Scenario`].
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Failure {
+    /// Nothing accepted the connection, the game wasn't running.
+    ConnectionRefused,
+    Timeout,
+    /// The game answered with a status other than `2xx`.
+    Status(u16),
+    /// The body wasn't the expected JSON, `body` is its start as in [`crate::Error::Decode`].
+    Decode {
+        body: String,
+        message: String,
+    },
+}
+
+impl Failure {
+    /// How the request failing with `err` failed, `None` for errors of the client rather than
+    /// the game, such as an invalid base URL.
+    pub fn from_error(err: &crate::Error) -> Option<Self> {
+        match err {
+            crate::Error::ConnectionRefused { .. } => Some(Failure::ConnectionRefused),
+            crate::Error::Timeout { .. } => Some(Failure::Timeout),
+            crate::Error::Status { status, .. } => Some(Failure::Status(*status)),
+            crate::Error::Decode { body, source, .. } => Some(Failure::Decode {
+                body: body.clone(),
+                message: source.to_string(),
+            }),
+            crate::Error::InvalidBaseUrl { .. } | crate::Error::Http(_) => None,
+        }
+    }
+
+    /// The error of a request to `endpoint` failing this way, without an underlying HTTP error.
+    pub fn to_error(&self, endpoint: &str) -> crate::Error {
+        let url = endpoint.to_string();
+        match self {
+            Failure::ConnectionRefused => crate::Error::ConnectionRefused { url, source: None },
+            Failure::Timeout => crate::Error::Timeout { url, source: None },
+            Failure::Status(status) => crate::Error::Status {
+                endpoint: url,
+                status: *status,
+            },
+            Failure::Decode { body, message } => crate::Error::Decode {
+                endpoint: url,
+                body: body.clone(),
+                source: serde_json::Error::custom(message),
+            },
+        }
+    }
+}
+
+/// The [`Failure`] of each endpoint, if it is failing.
+#[derive(Debug, Clone, Eq, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Failures {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub static_decklist: Option<Failure>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub positional_rectangles: Option<Failure>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expeditions_state: Option<Failure>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub game_result: Option<Failure>,
+}
+
+impl Failures {
+    pub fn is_empty(&self) -> bool {
+        *self == Failures::default()
+    }
+}
+
+/// What every endpoint answers at a point of a [`Scenario`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Snapshot {
+    pub static_decklist: StaticDecklist,
+    pub positional_rectangles: PositionalRectangles,
+    pub expeditions_state: ExpeditionsState,
+    pub game_result: GameResult,
+    /// Endpoints failing instead of answering, the responses above are what they answered last.
+    pub failures: Failures,
+}
+
+impl Default for Snapshot {
+    /// The game idling in the menus without having played a game.
+    fn default() -> Self {
+        Snapshot {
+            static_decklist: StaticDecklist {
+                deck_code: None,
+                cards_in_deck: None,
+            },
+            positional_rectangles: PositionalRectangles {
+                player_name: None,
+                opponent_name: None,
+                game_state: GameState::Menus,
+                screen: Screen {
+                    screen_width: 1920,
+                    screen_height: 1080,
+                },
+                rectangles: Vec::new(),
+            },
+            expeditions_state: ExpeditionsState {
+                is_active: false,
+                state: ExpeditionState::Inactive,
+                record: None,
+                draft_picks: None,
+                deck: None,
+                games: 0,
+                wins: 0,
+                losses: 0,
+            },
+            game_result: GameResult {
+                game_id: -1,
+                local_player_won: false,
+            },
+            failures: Failures::default(),
+        }
+    }
+}
+
+/// A timeline of Game Client API responses, stored as one JSON [`Event`] per line.
+///
+/// ```text
+/// {"at":0.0,"static-decklist":{"DeckCode":null,"CardsInDeck":null}}
+/// {"at":2.5,"game-result":{"GameID":1,"LocalPlayerWon":true}}
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Scenario {
+    /// Sorted by time.
+    events: Vec<Event>,
+    static_decklist: Positions,
+    positional_rectangles: Positions,
+    expeditions_state: Positions,
+    game_result: Positions,
+}
+
+impl Scenario {
+    /// Sorts `events` by time, keeping the order of events at the same time.
+    ///
+    /// Events with a negative or `NaN` time are at the start.
+    pub fn new(mut events: Vec<Event>) -> Self {
+        events.sort_by_key(|event| seconds(event.at));
+        Scenario {
+            static_decklist: Positions::new(&events, static_decklist),
+            positional_rectangles: Positions::new(&events, positional_rectangles),
+            expeditions_state: Positions::new(&events, expeditions_state),
+            game_result: Positions::new(&events, game_result),
+            events,
+        }
+    }
+
+    /// The events, sorted by time.
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Reads one event per line, skipping blank lines.
+    ///
+    /// Fails on lines that aren't an event or whose `at` isn't a representable number of
+    /// seconds.
+    pub fn read(reader: impl BufRead) -> io::Result<Self> {
+        let mut events = Vec::new();
+        for (number, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let invalid = |message| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: {}", number + 1, message),
+                )
+            };
+            let event: Event =
+                serde_json::from_str(&line).map_err(|err| invalid(err.to_string()))?;
+            if Duration::try_from_secs_f64(event.at).is_err() {
+                return Err(invalid(format!("invalid time {}", event.at)));
+            }
+            events.push(event);
+        }
+        Ok(Scenario::new(events))
+    }
+
+    pub fn write(&self, mut writer: impl Write) -> io::Result<()> {
+        for event in &self.events {
+            write_event(&mut writer, event)?;
+        }
+        writer.flush()
+    }
+
+    /// Time of the last event.
+    pub fn duration(&self) -> Duration {
+        self.events
+            .last()
+            .map_or(Duration::default(), |event| seconds(event.at))
+    }
+
+    /// The responses after every event up to `elapsed`, starting from [`Snapshot::default`].
+    pub fn snapshot_at(&self, elapsed: Duration) -> Snapshot {
+        let events = &self.events[..self
+            .events
+            .partition_point(|event| seconds(event.at) <= elapsed)];
+        let default = Snapshot::default();
+        let (static_decklist, static_decklist_failure) =
+            self.static_decklist
+                .latest(events, static_decklist, default.static_decklist);
+        let (positional_rectangles, positional_rectangles_failure) = self
+            .positional_rectangles
+            .latest(events, positional_rectangles, default.positional_rectangles);
+        let (expeditions_state, expeditions_state_failure) =
+            self.expeditions_state
+                .latest(events, expeditions_state, default.expeditions_state);
+        let (game_result, game_result_failure) =
+            self.game_result
+                .latest(events, game_result, default.game_result);
+
+        Snapshot {
+            static_decklist,
+            positional_rectangles,
+            expeditions_state,
+            game_result,
+            failures: Failures {
+                static_decklist: static_decklist_failure,
+                positional_rectangles: positional_rectangles_failure,
+                expeditions_state: expeditions_state_failure,
+                game_result: game_result_failure,
+            },
+        }
+    }
+}
+
+/// Positions of the events with a response or a failure of one endpoint, so
+/// [`Scenario::snapshot_at`] finds the latest ones with a binary search.
+#[derive(Debug, Clone, PartialEq, Default)]
+struct Positions {
+    responses: Vec<usize>,
+    failures: Vec<usize>,
+}
+
+impl Positions {
+    fn new<T>(
+        events: &[Event],
+        endpoint: impl Fn(&Event) -> (&Option<T>, &Option<Failure>),
+    ) -> Self {
+        let mut positions = Positions::default();
+        for (position, event) in events.iter().enumerate() {
+            let (response, failure) = endpoint(event);
+            if response.is_some() {
+                positions.responses.push(position);
+            }
+            if failure.is_some() {
+                positions.failures.push(position);
+            }
+        }
+        positions
+    }
+
+    /// What the endpoint answers after `events`, the first events of the scenario.
+    ///
+    /// A response ends a failure, a failure in the same event as a response wins.
+    fn latest<T: Clone>(
+        &self,
+        events: &[Event],
+        endpoint: impl Fn(&Event) -> (&Option<T>, &Option<Failure>),
+        default: T,
+    ) -> (T, Option<Failure>) {
+        let last = |positions: &[usize]| {
+            let count = positions.partition_point(|position| *position < events.len());
+            positions[..count].last().copied()
+        };
+        let response = last(&self.responses);
+        let failure = last(&self.failures).filter(|failure| Some(*failure) >= response);
+        (
+            response
+                .and_then(|position| endpoint(&events[position]).0.clone())
+                .unwrap_or(default),
+            failure.and_then(|position| endpoint(&events[position]).1.clone()),
+        )
+    }
+}
+
+fn static_decklist(event: &Event) -> (&Option<StaticDecklist>, &Option<Failure>) {
+    (&event.static_decklist, &event.failures.static_decklist)
+}
+
+fn positional_rectangles(event: &Event) -> (&Option<PositionalRectangles>, &Option<Failure>) {
+    (
+        &event.positional_rectangles,
+        &event.failures.positional_rectangles,
+    )
+}
+
+fn expeditions_state(event: &Event) -> (&Option<ExpeditionsState>, &Option<Failure>) {
+    (&event.expeditions_state, &event.failures.expeditions_state)
+}
+
+fn game_result(event: &Event) -> (&Option<GameResult>, &Option<Failure>) {
+    (&event.game_result, &event.failures.game_result)
+}
+
+pub(crate) fn write_event(mut writer: impl Write, event: &Event) -> io::Result<()> {
+    serde_json::to_writer(&mut writer, event)?;
+    writer.write_all(b"\n")
+}
+
+/// `at` as a duration, events built in code with a negative or `NaN` time are at the start and
+/// ones too far out for a `Duration` at its end.
+fn seconds(at: f64) -> Duration {
+    Duration::try_from_secs_f64(at.max(0.0)).unwrap_or(Duration::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Event, Failure, Failures, GameResult, Scenario, Snapshot};
+    use std::time::Duration;
+
+    fn game_result(at: f64, game_id: i32, failure: Option<Failure>) -> Event {
+        Event {
+            at,
+            game_result: Some(GameResult {
+                game_id,
+                local_player_won: true,
+            })
+            .filter(|_| game_id >= 0),
+            failures: Failures {
+                game_result: failure,
+                ..Failures::default()
+            },
+            ..Event::default()
+        }
+    }
+
+    #[test]
+    fn test_snapshot_at() {
+        let scenario = Scenario::new(vec![
+            game_result(3.0, 2, None),
+            game_result(2.0, 1, Some(Failure::Timeout)),
+            game_result(f64::NAN, 0, None),
+            game_result(1.0, -1, Some(Failure::ConnectionRefused)),
+        ]);
+        let ids: Vec<_> = scenario
+            .events()
+            .iter()
+            .map(|event| event.game_result.as_ref().map(|result| result.game_id))
+            .collect();
+        assert_eq!(ids, vec![Some(0), None, Some(1), Some(2)]);
+
+        let at = |seconds| {
+            scenario
+                .snapshot_at(Duration::from_secs(seconds))
+                .game_result
+        };
+        assert_eq!(at(0).game_id, 0);
+        assert_eq!(at(2).game_id, 1);
+        assert_eq!(at(3).game_id, 2);
+
+        let failure = |seconds| {
+            scenario
+                .snapshot_at(Duration::from_secs(seconds))
+                .failures
+                .game_result
+        };
+        assert_eq!(failure(0), None);
+        assert_eq!(failure(1), Some(Failure::ConnectionRefused));
+        // A failure in the same event as a response wins, a later response ends it.
+        assert_eq!(failure(2), Some(Failure::Timeout));
+        assert_eq!(failure(3), None);
+
+        assert_eq!(
+            Scenario::default().snapshot_at(Duration::MAX),
+            Snapshot::default()
+        );
+    }
+
+    #[test]
+    fn test_read_times() {
+        let scenario = Scenario::read(&b"{\"at\":2.5}\n\n{\"at\":1}\n"[..]).unwrap();
+        let times: Vec<_> = scenario.events().iter().map(|event| event.at).collect();
+        assert_eq!(times, vec![1.0, 2.5]);
+        assert_eq!(scenario.duration(), Duration::from_millis(2500));
+
+        for line in &["{\"at\":-1}", "{\"at\":1e300}", "{\"at\":null}"] {
+            let err = Scenario::read(line.as_bytes()).unwrap_err();
+            assert!(err.to_string().starts_with("line 1: "), "{}", err);
+        }
+
+        let scenario = Scenario::new(vec![Event {
+            at: f64::INFINITY,
+            ..Event::default()
+        }]);
+        assert_eq!(scenario.duration(), Duration::MAX);
+    }
+}
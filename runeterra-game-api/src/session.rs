@@ -0,0 +1,389 @@
+use crate::scenario::write_event;
+use crate::{
+    Event, ExpeditionsState, Failure, Failures, GameApi, GameResult, PositionalRectangles,
+    Scenario, Snapshot, StaticDecklist,
+};
+use async_trait::async_trait;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+struct Recording<W> {
+    writer: W,
+    /// Time of the first response or failure, the start of the session.
+    start: Option<Instant>,
+    /// The last response and failure of each endpoint.
+    last: Event,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> Recording<W> {
+    fn record<T: Clone + PartialEq>(
+        &mut self,
+        response: &Result<T, crate::Error>,
+        field: fn(&mut Event) -> &mut Option<T>,
+        failure: fn(&mut Failures) -> &mut Option<Failure>,
+    ) {
+        let mut event = Event::default();
+        match response {
+            Ok(response) => {
+                if field(&mut self.last).as_ref() == Some(response)
+                    && failure(&mut self.last.failures).is_none()
+                {
+                    return;
+                }
+                *field(&mut self.last) = Some(response.clone());
+                *failure(&mut self.last.failures) = None;
+                *field(&mut event) = Some(response.clone());
+            }
+            Err(err) => {
+                let new_failure = match Failure::from_error(err) {
+                    Some(new_failure) => new_failure,
+                    None => return,
+                };
+                if failure(&mut self.last.failures).as_ref() == Some(&new_failure) {
+                    return;
+                }
+                *failure(&mut self.last.failures) = Some(new_failure.clone());
+                *failure(&mut event.failures) = Some(new_failure);
+            }
+        }
+
+        // The first response starts the session, exactly at 0.
+        let at = match self.start {
+            Some(start) => start.elapsed().as_secs_f64(),
+            None => {
+                self.start = Some(Instant::now());
+                0.0
+            }
+        };
+        event.at = at;
+        if let Err(err) = write_event(&mut self.writer, &event).and_then(|_| self.writer.flush()) {
+            self.error.get_or_insert(err);
+        }
+    }
+}
+
+/// A [`GameApi`] passing requests on to another one and writing the responses to a session file.
+///
+/// The file is a [`Scenario`], so it can be played back with a [`Replayer`] or served by the fake
+/// game client. To keep it small a response is only written when it differs from the previous
+/// one of its endpoint. Failed requests are written as a [`Failure`] so they replay too, except
+/// errors of the client itself such as an invalid base URL. Clones write to the same file.
+#[derive(Debug)]
+pub struct Recorder<A, W> {
+    api: A,
+    recording: Arc<Mutex<Recording<W>>>,
+}
+
+impl<A: Clone, W> Clone for Recorder<A, W> {
+    fn clone(&self) -> Self {
+        Recorder {
+            api: self.api.clone(),
+            recording: self.recording.clone(),
+        }
+    }
+}
+
+impl<A, W: Write> Recorder<A, W> {
+    /// Records the responses of `api` to `writer`, timed from the first one.
+    pub fn new(api: A, writer: W) -> Self {
+        Recorder {
+            api,
+            recording: Arc::new(Mutex::new(Recording {
+                writer,
+                start: None,
+                last: Event::default(),
+                error: None,
+            })),
+        }
+    }
+
+    pub fn api(&self) -> &A {
+        &self.api
+    }
+
+    /// Returns the first error writing the session, if any, and flushes the writer.
+    pub fn flush(&self) -> io::Result<()> {
+        let mut recording = self.recording.lock().unwrap();
+        match recording.error.take() {
+            Some(err) => Err(err),
+            None => recording.writer.flush(),
+        }
+    }
+
+    fn record<T: Clone + PartialEq>(
+        &self,
+        response: Result<T, crate::Error>,
+        field: fn(&mut Event) -> &mut Option<T>,
+        failure: fn(&mut Failures) -> &mut Option<Failure>,
+    ) -> Result<T, crate::Error> {
+        self.recording
+            .lock()
+            .unwrap()
+            .record(&response, field, failure);
+        response
+    }
+}
+
+#[async_trait]
+impl<A, W> GameApi for Recorder<A, W>
+where
+    A: GameApi + Send + Sync,
+    W: Write + Send,
+{
+    async fn get_static_decklist(&self) -> Result<StaticDecklist, crate::Error> {
+        let response = self.api.get_static_decklist().await;
+        self.record(
+            response,
+            |event| &mut event.static_decklist,
+            |failures| &mut failures.static_decklist,
+        )
+    }
+
+    async fn get_positional_rectangles(&self) -> Result<PositionalRectangles, crate::Error> {
+        let response = self.api.get_positional_rectangles().await;
+        self.record(
+            response,
+            |event| &mut event.positional_rectangles,
+            |failures| &mut failures.positional_rectangles,
+        )
+    }
+
+    async fn get_expeditions_state(&self) -> Result<ExpeditionsState, crate::Error> {
+        let response = self.api.get_expeditions_state().await;
+        self.record(
+            response,
+            |event| &mut event.expeditions_state,
+            |failures| &mut failures.expeditions_state,
+        )
+    }
+
+    async fn get_game_result(&self) -> Result<GameResult, crate::Error> {
+        let response = self.api.get_game_result().await;
+        self.record(
+            response,
+            |event| &mut event.game_result,
+            |failures| &mut failures.game_result,
+        )
+    }
+}
+
+/// A [`GameApi`] answering with the responses of a recorded session or scenario, as they were
+/// at the same time since the start of the replay.
+#[derive(Debug, Clone)]
+pub struct Replayer {
+    scenario: Arc<Scenario>,
+    start: Instant,
+    /// Time into the scenario at `start`.
+    offset: Duration,
+    speed: f64,
+    looping: bool,
+}
+
+impl Replayer {
+    /// Starts replaying `scenario` in real time.
+    pub fn new(scenario: Scenario) -> Self {
+        Replayer::with_speed(scenario, 1.0)
+    }
+
+    /// Starts replaying `scenario` `speed` times faster than real time, `0.0` keeps it paused
+    /// wherever it is [`seek`](Replayer::seek)ed to.
+    pub fn with_speed(scenario: Scenario, speed: f64) -> Self {
+        Replayer {
+            scenario: Arc::new(scenario),
+            start: Instant::now(),
+            offset: Duration::default(),
+            speed,
+            looping: false,
+        }
+    }
+
+    /// Restarts the scenario after its last event instead of staying in its final state.
+    pub fn looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Jumps to `elapsed` into the scenario, the replay goes on from there.
+    pub fn seek(&mut self, elapsed: Duration) {
+        self.start = Instant::now();
+        self.offset = elapsed;
+    }
+
+    /// Time into the scenario.
+    pub fn elapsed(&self) -> Duration {
+        let elapsed = Duration::try_from_secs_f64(self.start.elapsed().as_secs_f64() * self.speed)
+            .map_or(Duration::MAX, |elapsed| elapsed.saturating_add(self.offset));
+        let duration = self.scenario.duration();
+        if self.looping && duration > Duration::default() {
+            Duration::from_nanos((elapsed.as_nanos() % duration.as_nanos()) as u64)
+        } else {
+            elapsed
+        }
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        self.scenario.snapshot_at(self.elapsed())
+    }
+}
+
+fn replay<T>(response: T, failure: &Option<Failure>, endpoint: &str) -> Result<T, crate::Error> {
+    match failure {
+        Some(failure) => Err(failure.to_error(endpoint)),
+        None => Ok(response),
+    }
+}
+
+#[async_trait]
+impl GameApi for Replayer {
+    async fn get_static_decklist(&self) -> Result<StaticDecklist, crate::Error> {
+        let snapshot = self.snapshot();
+        replay(
+            snapshot.static_decklist,
+            &snapshot.failures.static_decklist,
+            "/static-decklist",
+        )
+    }
+
+    async fn get_positional_rectangles(&self) -> Result<PositionalRectangles, crate::Error> {
+        let snapshot = self.snapshot();
+        replay(
+            snapshot.positional_rectangles,
+            &snapshot.failures.positional_rectangles,
+            "/positional-rectangles",
+        )
+    }
+
+    async fn get_expeditions_state(&self) -> Result<ExpeditionsState, crate::Error> {
+        let snapshot = self.snapshot();
+        replay(
+            snapshot.expeditions_state,
+            &snapshot.failures.expeditions_state,
+            "/expeditions-state",
+        )
+    }
+
+    async fn get_game_result(&self) -> Result<GameResult, crate::Error> {
+        let snapshot = self.snapshot();
+        replay(
+            snapshot.game_result,
+            &snapshot.failures.game_result,
+            "/game-result",
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Failure, GameApi, GameResult, Recorder, Replayer, Scenario, ScriptedApi};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_record_and_replay() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let first = GameResult {
+            game_id: 1,
+            local_player_won: false,
+        };
+        let second = GameResult {
+            game_id: 2,
+            local_player_won: true,
+        };
+
+        let api = ScriptedApi::new();
+        api.push_game_result(Ok(first.clone()));
+        api.push_game_result(Ok(first.clone()));
+        api.push_game_result(Ok(second.clone()));
+
+        let recorder = Recorder::new(api, Vec::new());
+        assert_eq!(recorder.get_game_result().await?, first);
+        assert_eq!(recorder.get_game_result().await?, first);
+        tokio::time::delay_for(Duration::from_millis(100)).await;
+        assert_eq!(recorder.get_game_result().await?, second);
+        recorder.flush().unwrap();
+
+        let session = recorder.recording.lock().unwrap().writer.clone();
+        let scenario = Scenario::read(&session[..]).unwrap();
+        assert_eq!(scenario.events().len(), 2);
+        assert_eq!(scenario.events()[0].at, 0.0);
+        let duration = scenario.duration();
+        assert!(duration >= Duration::from_millis(100));
+
+        // Paused and seeked rather than slept through, so the timing of the machine can't matter.
+        let mut replayer = Replayer::with_speed(scenario, 0.0);
+        assert_eq!(replayer.get_game_result().await?, first);
+        replayer.seek(duration);
+        assert_eq!(replayer.get_game_result().await?, second);
+        replayer.seek(duration / 2);
+        assert_eq!(replayer.get_game_result().await?, first);
+        assert_eq!(replayer.elapsed(), duration / 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_record_and_replay_failures() -> std::result::Result<(), Box<dyn std::error::Error>>
+    {
+        let result = GameResult {
+            game_id: 1,
+            local_player_won: true,
+        };
+        let refused = || crate::Error::ConnectionRefused {
+            url: "http://localhost:21337/game-result".to_string(),
+            source: None,
+        };
+
+        let api = ScriptedApi::new();
+        api.push_game_result(Err(refused()));
+        api.push_game_result(Err(refused()));
+        api.push_game_result(Ok(result.clone()));
+        api.push_game_result(Err(crate::Error::Status {
+            endpoint: "/game-result".to_string(),
+            status: 503,
+        }));
+        api.push_game_result(Ok(result.clone()));
+
+        let recorder = Recorder::new(api, Vec::new());
+        for _ in 0..5 {
+            let _ = recorder.get_game_result().await;
+        }
+        recorder.flush()?;
+
+        let session = recorder.recording.lock().unwrap().writer.clone();
+        let scenario = Scenario::read(&session[..])?;
+        let failures: Vec<_> = scenario
+            .events()
+            .iter()
+            .map(|event| event.failures.game_result.clone())
+            .collect();
+        assert_eq!(
+            failures,
+            vec![
+                Some(Failure::ConnectionRefused),
+                None,
+                Some(Failure::Status(503)),
+                None
+            ]
+        );
+
+        let times: Vec<_> = scenario.events().iter().map(|event| event.at).collect();
+        let mut replayer = Replayer::with_speed(scenario, 0.0);
+        let err = replayer.get_game_result().await.unwrap_err();
+        assert!(matches!(err, crate::Error::ConnectionRefused { .. }));
+        assert!(err.is_transient());
+        replayer.seek(Duration::from_secs_f64(times[1]));
+        assert_eq!(replayer.get_game_result().await?, result);
+        replayer.seek(Duration::from_secs_f64(times[2]));
+        match replayer.get_game_result().await {
+            Err(crate::Error::Status { endpoint, status }) => {
+                assert_eq!((endpoint.as_str(), status), ("/game-result", 503))
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        replayer.seek(Duration::from_secs_f64(times[3]));
+        assert_eq!(replayer.get_game_result().await?, result);
+
+        Ok(())
+    }
+}
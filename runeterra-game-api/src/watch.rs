@@ -0,0 +1,160 @@
+use crate::{Client, ExpeditionsState, GameApi, GameResult, PositionalRectangles, StaticDecklist};
+use futures::stream::{self, BoxStream, Stream, StreamExt};
+use std::future::Future;
+use std::time::Duration;
+
+/// How often [`Client::watch`] polls each endpoint, `None` to not poll it at all.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct WatchConfig {
+    pub static_decklist: Option<Duration>,
+    pub positional_rectangles: Option<Duration>,
+    pub expeditions_state: Option<Duration>,
+    pub game_result: Option<Duration>,
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        WatchConfig {
+            static_decklist: Some(Duration::from_secs(1)),
+            positional_rectangles: Some(Duration::from_millis(250)),
+            expeditions_state: Some(Duration::from_secs(2)),
+            game_result: Some(Duration::from_secs(1)),
+        }
+    }
+}
+
+/// A payload that differs from the previous one returned by the same endpoint.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Change {
+    Deck(StaticDecklist),
+    Rectangles(PositionalRectangles),
+    Expedition(ExpeditionsState),
+    /// A game ended, never the result already reported when watching started.
+    GameResult(GameResult),
+}
+
+struct Poller<A, T> {
+    api: A,
+    last: Option<T>,
+    failing: bool,
+    first: bool,
+}
+
+/// Polls one endpoint every `interval`, yielding only the payloads that differ from the last
+/// one, and the first error of every run of failed requests.
+///
+/// Unless `yield_first`, the first payload is only remembered to compare the next ones with.
+fn poll<A, T, F, Fut>(
+    api: A,
+    interval: Duration,
+    fetch: F,
+    change: fn(T) -> Change,
+    yield_first: bool,
+) -> BoxStream<'static, Result<Change, crate::Error>>
+where
+    A: Clone + Send + 'static,
+    T: Clone + PartialEq + Send + 'static,
+    F: Fn(A) -> Fut + Copy + Send + 'static,
+    Fut: Future<Output = Result<T, crate::Error>> + Send,
+{
+    let poller = Poller {
+        api,
+        last: None,
+        failing: false,
+        first: true,
+    };
+
+    stream::unfold(poller, move |mut poller| async move {
+        loop {
+            if !poller.first {
+                tokio::time::delay_for(interval).await;
+            }
+            poller.first = false;
+
+            match fetch(poller.api.clone()).await {
+                Ok(value) => {
+                    poller.failing = false;
+                    if poller.last.is_none() && !yield_first {
+                        poller.last = Some(value);
+                    } else if poller.last.as_ref() != Some(&value) {
+                        poller.last = Some(value.clone());
+                        return Some((Ok(change(value)), poller));
+                    }
+                }
+                Err(err) if !poller.failing => {
+                    poller.failing = true;
+                    return Some((Err(err), poller));
+                }
+                Err(_) => {}
+            }
+        }
+    })
+    .boxed()
+}
+
+impl Client {
+    /// Polls every endpoint enabled in `config` at its own interval, see [`watch`].
+    pub fn watch(
+        &self,
+        config: WatchConfig,
+    ) -> impl Stream<Item = Result<Change, crate::Error>> + Send + 'static {
+        watch(self.clone(), config)
+    }
+}
+
+/// Polls every endpoint of `api` enabled in `config` at its own interval, yielding a [`Change`]
+/// the first time a payload is seen and whenever it differs from the previous one.
+///
+/// The game result is the exception, the first one seen is of a game that ended before
+/// watching, so it is only yielded once it changes.
+///
+/// Failed requests are yielded once, until the endpoint responds again, so a closed game doesn't
+/// flood the stream. The stream never ends, drop it to stop polling.
+pub fn watch<A>(
+    api: A,
+    config: WatchConfig,
+) -> impl Stream<Item = Result<Change, crate::Error>> + Send + 'static
+where
+    A: GameApi + Clone + Send + Sync + 'static,
+{
+    let mut pollers = Vec::new();
+
+    if let Some(interval) = config.static_decklist {
+        pollers.push(poll(
+            api.clone(),
+            interval,
+            |api: A| async move { api.get_static_decklist().await },
+            Change::Deck,
+            true,
+        ));
+    }
+    if let Some(interval) = config.positional_rectangles {
+        pollers.push(poll(
+            api.clone(),
+            interval,
+            |api: A| async move { api.get_positional_rectangles().await },
+            Change::Rectangles,
+            true,
+        ));
+    }
+    if let Some(interval) = config.expeditions_state {
+        pollers.push(poll(
+            api.clone(),
+            interval,
+            |api: A| async move { api.get_expeditions_state().await },
+            Change::Expedition,
+            true,
+        ));
+    }
+    if let Some(interval) = config.game_result {
+        pollers.push(poll(
+            api,
+            interval,
+            |api: A| async move { api.get_game_result().await },
+            Change::GameResult,
+            false,
+        ));
+    }
+
+    stream::select_all(pollers)
+}